@@ -0,0 +1,227 @@
+//! Audio format conversion (requires the `audio-convert` feature)
+
+use crate::AudioFormat;
+
+/// Layout of raw 16-bit little-endian PCM audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PcmSpec {
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl Default for PcmSpec {
+    /// 16 kHz mono, the input most speech-to-text providers expect
+    fn default() -> Self {
+        Self {
+            sample_rate: 16_000,
+            channels: 1,
+        }
+    }
+}
+
+const BITS_PER_SAMPLE: u16 = 16;
+
+/// Audio conversion error types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AudioError {
+    /// No converter exists between the two formats
+    Unsupported { from: AudioFormat, to: AudioFormat },
+    /// The input is not valid data in the source format
+    InvalidData(String),
+    /// The audio or its layout is too large for the target container
+    TooLarge(String),
+}
+
+impl std::fmt::Display for AudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported { from, to } => {
+                write!(f, "Conversion from {:?} to {:?} is not supported", from, to)
+            }
+            Self::InvalidData(msg) => write!(f, "Invalid audio data: {}", msg),
+            Self::TooLarge(msg) => write!(f, "Audio too large: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AudioError {}
+
+/// Convert audio between formats.
+///
+/// Supports WAV (16-bit PCM) to raw PCM and back. Raw PCM carries no
+/// header, so PCM input is assumed to use the default `PcmSpec`; call
+/// [`pcm_to_wav`] directly for other layouts. Converting a format to
+/// itself returns the data unchanged.
+pub fn convert_audio(
+    data: &[u8],
+    from: AudioFormat,
+    to: AudioFormat,
+) -> Result<Vec<u8>, AudioError> {
+    match (from, to) {
+        _ if from == to => Ok(data.to_vec()),
+        (AudioFormat::Wav, AudioFormat::Pcm) => wav_to_pcm(data).map(|(pcm, _)| pcm),
+        (AudioFormat::Pcm, AudioFormat::Wav) => pcm_to_wav(data, PcmSpec::default()),
+        _ => Err(AudioError::Unsupported { from, to }),
+    }
+}
+
+/// Wrap raw 16-bit PCM samples in a WAV container.
+///
+/// Fails with `AudioError::TooLarge` if the layout or the data does not
+/// fit the 32-bit fields of the WAV header, e.g. more than 4 GiB of
+/// samples.
+pub fn pcm_to_wav(pcm: &[u8], spec: PcmSpec) -> Result<Vec<u8>, AudioError> {
+    let too_large = |msg: &str| AudioError::TooLarge(msg.to_string());
+    let block_align = spec
+        .channels
+        .checked_mul(BITS_PER_SAMPLE / 8)
+        .ok_or_else(|| too_large("too many channels"))?;
+    let byte_rate = spec
+        .sample_rate
+        .checked_mul(block_align as u32)
+        .ok_or_else(|| too_large("byte rate does not fit in 32 bits"))?;
+    let data_len = u32::try_from(pcm.len())
+        .ok()
+        .filter(|len| len.checked_add(36).is_some())
+        .ok_or_else(|| too_large("PCM data exceeds the 4 GiB WAV limit"))?;
+
+    let mut wav = Vec::with_capacity(44 + pcm.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&spec.channels.to_le_bytes());
+    wav.extend_from_slice(&spec.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&byte_rate.to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&BITS_PER_SAMPLE.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    wav.extend_from_slice(pcm);
+    Ok(wav)
+}
+
+/// Extract the raw PCM samples and their layout from a 16-bit PCM WAV file
+pub fn wav_to_pcm(wav: &[u8]) -> Result<(Vec<u8>, PcmSpec), AudioError> {
+    let invalid = |msg: &str| AudioError::InvalidData(msg.to_string());
+    let u16_at = |offset: usize| u16::from_le_bytes([wav[offset], wav[offset + 1]]);
+    let u32_at = |offset: usize| {
+        u32::from_le_bytes([
+            wav[offset],
+            wav[offset + 1],
+            wav[offset + 2],
+            wav[offset + 3],
+        ])
+    };
+
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return Err(invalid("missing RIFF/WAVE header"));
+    }
+
+    let mut spec = None;
+    let mut offset = 12;
+    while offset + 8 <= wav.len() {
+        let id = &wav[offset..offset + 4];
+        let size = u32_at(offset + 4) as usize;
+        let body = offset + 8;
+        let end = body
+            .checked_add(size)
+            .filter(|end| *end <= wav.len())
+            .ok_or_else(|| invalid("chunk extends past end of file"))?;
+
+        match id {
+            b"fmt " => {
+                if size < 16 {
+                    return Err(invalid("fmt chunk too short"));
+                }
+                if u16_at(body) != 1 || u16_at(body + 14) != BITS_PER_SAMPLE {
+                    return Err(invalid("only 16-bit PCM WAV is supported"));
+                }
+                spec = Some(PcmSpec {
+                    channels: u16_at(body + 2),
+                    sample_rate: u32_at(body + 4),
+                });
+            }
+            b"data" => {
+                let spec = spec.ok_or_else(|| invalid("data chunk before fmt chunk"))?;
+                return Ok((wav[body..end].to_vec(), spec));
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size
+        offset = end + (size & 1);
+    }
+    Err(invalid("missing data chunk"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wav_pcm_round_trip() {
+        let pcm: Vec<u8> = (0..64u8).collect();
+
+        let wav = convert_audio(&pcm, AudioFormat::Pcm, AudioFormat::Wav).unwrap();
+        assert_eq!(wav.len(), 44 + pcm.len());
+        assert_eq!(wav_to_pcm(&wav).unwrap().1, PcmSpec::default());
+
+        let back = convert_audio(&wav, AudioFormat::Wav, AudioFormat::Pcm).unwrap();
+        assert_eq!(back, pcm);
+    }
+
+    #[test]
+    fn test_wav_preserves_spec() {
+        let spec = PcmSpec {
+            sample_rate: 44_100,
+            channels: 2,
+        };
+        let wav = pcm_to_wav(&[1, 2, 3, 4], spec).unwrap();
+        assert_eq!(wav_to_pcm(&wav).unwrap(), (vec![1, 2, 3, 4], spec));
+    }
+
+    #[test]
+    fn test_convert_errors() {
+        assert_eq!(
+            convert_audio(&[], AudioFormat::Mp3, AudioFormat::Wav),
+            Err(AudioError::Unsupported {
+                from: AudioFormat::Mp3,
+                to: AudioFormat::Wav
+            })
+        );
+        assert!(matches!(
+            convert_audio(b"not a wav file", AudioFormat::Wav, AudioFormat::Pcm),
+            Err(AudioError::InvalidData(_))
+        ));
+    }
+
+    #[test]
+    fn test_pcm_to_wav_rejects_oversized_spec() {
+        // 4096 channels once overflowed the bit count; the block size fits
+        let wide = PcmSpec {
+            sample_rate: 8_000,
+            channels: 4096,
+        };
+        assert!(pcm_to_wav(&[], wide).is_ok());
+
+        let too_many_channels = PcmSpec {
+            sample_rate: 16_000,
+            channels: u16::MAX,
+        };
+        assert!(matches!(
+            pcm_to_wav(&[], too_many_channels),
+            Err(AudioError::TooLarge(_))
+        ));
+
+        let byte_rate_overflow = PcmSpec {
+            sample_rate: u32::MAX,
+            channels: 2,
+        };
+        assert!(matches!(
+            pcm_to_wav(&[], byte_rate_overflow),
+            Err(AudioError::TooLarge(_))
+        ));
+    }
+}
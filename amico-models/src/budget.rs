@@ -0,0 +1,226 @@
+//! Spend limits for language models
+
+use std::sync::Mutex;
+
+use crate::{LanguageInput, LanguageModel, LanguageOutput, Model, TokenUsage};
+
+/// Model pricing in dollars per 1K tokens
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Pricing {
+    pub prompt_per_1k: f64,
+    pub completion_per_1k: f64,
+}
+
+impl Pricing {
+    pub fn new(prompt_per_1k: f64, completion_per_1k: f64) -> Self {
+        Self {
+            prompt_per_1k,
+            completion_per_1k,
+        }
+    }
+
+    /// Estimated cost of the given usage in dollars
+    pub fn cost(&self, usage: &TokenUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.prompt_per_1k
+            + usage.completion_tokens as f64 * self.completion_per_1k)
+            / 1000.0
+    }
+}
+
+/// Budget guard error types
+#[derive(Debug)]
+pub enum BudgetError<E> {
+    /// The budget was used up by previous calls; the model was not called
+    BudgetExceeded(TokenUsage),
+    /// The wrapped model failed
+    Model(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for BudgetError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BudgetExceeded(usage) => {
+                write!(f, "Budget exceeded after {} tokens", usage.total_tokens)
+            }
+            Self::Model(err) => write!(f, "Model error: {}", err),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for BudgetError<E> {}
+
+/// Wrapper that caps the cumulative spend of a language model.
+///
+/// Token usage is accumulated across calls. Once it crosses the token
+/// limit or the estimated dollar limit, every further call fails with
+/// `BudgetError::BudgetExceeded` without reaching the model. The call that
+/// crosses the limit still succeeds, since its cost is only known after
+/// it returns.
+///
+/// ```rust,ignore
+/// let model = BudgetGuard::new(provider)
+///     .with_pricing(Pricing::new(0.003, 0.015))
+///     .with_max_cost(5.0);
+/// ```
+pub struct BudgetGuard<M> {
+    inner: M,
+    pricing: Pricing,
+    max_tokens: Option<usize>,
+    max_cost: Option<f64>,
+    usage: Mutex<TokenUsage>,
+}
+
+impl<M> BudgetGuard<M> {
+    /// Wrap `inner` with no limits configured
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            pricing: Pricing::default(),
+            max_tokens: None,
+            max_cost: None,
+            usage: Mutex::new(TokenUsage::default()),
+        }
+    }
+
+    /// Set the pricing used to estimate cost
+    pub fn with_pricing(mut self, pricing: Pricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Limit the total number of tokens
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Limit the estimated cost in dollars
+    pub fn with_max_cost(mut self, max_cost: f64) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Token usage accumulated so far
+    pub fn usage(&self) -> TokenUsage {
+        *self.usage.lock().unwrap()
+    }
+
+    /// Estimated cost accumulated so far, in dollars
+    pub fn cost(&self) -> f64 {
+        self.pricing.cost(&self.usage())
+    }
+
+    /// Whether the budget has been used up
+    pub fn is_exceeded(&self) -> bool {
+        let usage = self.usage();
+        self.max_tokens.is_some_and(|max| usage.total_tokens >= max)
+            || self
+                .max_cost
+                .is_some_and(|max| self.pricing.cost(&usage) >= max)
+    }
+
+    /// Clear the accumulated usage
+    pub fn reset(&self) {
+        *self.usage.lock().unwrap() = TokenUsage::default();
+    }
+
+    /// Get a reference to the wrapped model
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+impl<M> Model for BudgetGuard<M>
+where
+    M: LanguageModel + Sync,
+    M::Context: Sync,
+{
+    type Context = M::Context;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = BudgetError<M::Error>;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        if self.is_exceeded() {
+            return Err(BudgetError::BudgetExceeded(self.usage()));
+        }
+
+        let output = self
+            .inner
+            .execute(context, input)
+            .await
+            .map_err(BudgetError::Model)?;
+
+        *self.usage.lock().unwrap() += output.usage;
+        Ok(output)
+    }
+}
+
+impl<M> LanguageModel for BudgetGuard<M>
+where
+    M: LanguageModel + Sync,
+    M::Context: Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EchoModel;
+
+    #[tokio::test]
+    async fn test_budget_token_limit() {
+        // The echo model reports one prompt and one completion token per word
+        let model = BudgetGuard::new(EchoModel::new()).with_max_tokens(10);
+
+        for _ in 0..2 {
+            model
+                .execute(&(), LanguageInput::new("one two three"))
+                .await
+                .unwrap();
+        }
+        assert_eq!(model.usage().total_tokens, 12);
+        assert!(model.is_exceeded());
+
+        let err = model
+            .execute(&(), LanguageInput::new("one more"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BudgetError::BudgetExceeded(usage) if usage.total_tokens == 12));
+
+        model.reset();
+        assert!(model
+            .execute(&(), LanguageInput::new("again"))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_budget_cost_limit() {
+        let model = BudgetGuard::new(EchoModel::new())
+            .with_pricing(Pricing::new(1.0, 2.0))
+            .with_max_cost(0.01);
+
+        // 2 prompt + 2 completion tokens cost $0.006
+        model
+            .execute(&(), LanguageInput::new("hello world"))
+            .await
+            .unwrap();
+        assert!(!model.is_exceeded());
+        assert!((model.cost() - 0.006).abs() < 1e-9);
+
+        model
+            .execute(&(), LanguageInput::new("hello world"))
+            .await
+            .unwrap();
+        let err = model
+            .execute(&(), LanguageInput::new("hello world"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BudgetError::BudgetExceeded(_)));
+    }
+}
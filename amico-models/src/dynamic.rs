@@ -0,0 +1,110 @@
+//! Object-safe facade for language models
+
+use futures::future::BoxFuture;
+use futures::FutureExt;
+
+use crate::{LanguageInput, LanguageModel, LanguageOutput, Model};
+
+/// Type-erased model error
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Object-safe counterpart of [`LanguageModel`].
+///
+/// `Model` uses associated types and `impl Future`, so it cannot be used as
+/// a trait object. This facade boxes the future and the error instead, so
+/// plugin hosts that pick a model at runtime can hold a
+/// `Box<dyn DynLanguageModel<C>>`. Every `LanguageModel` with a
+/// thread-safe error implements it automatically, and the boxed facade
+/// implements `LanguageModel` again.
+///
+/// ```rust,ignore
+/// let model: Box<dyn DynLanguageModel<()>> = if offline {
+///     Box::new(EchoModel::new())
+/// } else {
+///     Box::new(provider.language_model())
+/// };
+/// ```
+pub trait DynLanguageModel<C>: Send + Sync {
+    /// Execute the model, boxing the returned future
+    fn execute_dyn<'a>(
+        &'a self,
+        context: &'a C,
+        input: LanguageInput,
+    ) -> BoxFuture<'a, Result<LanguageOutput, BoxError>>;
+}
+
+impl<M> DynLanguageModel<M::Context> for M
+where
+    M: LanguageModel + Send + Sync,
+    M::Error: std::error::Error + Send + Sync + 'static,
+{
+    fn execute_dyn<'a>(
+        &'a self,
+        context: &'a M::Context,
+        input: LanguageInput,
+    ) -> BoxFuture<'a, Result<LanguageOutput, BoxError>> {
+        self.execute(context, input)
+            .map(|result| result.map_err(BoxError::from))
+            .boxed()
+    }
+}
+
+impl<C: Sync> Model for Box<dyn DynLanguageModel<C>> {
+    type Context = C;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = BoxError;
+
+    fn execute<'a>(
+        &'a self,
+        context: &'a C,
+        input: LanguageInput,
+    ) -> impl std::future::Future<Output = Result<LanguageOutput, BoxError>> + Send + 'a {
+        (**self).execute_dyn(context, input)
+    }
+}
+
+impl<C: Sync> LanguageModel for Box<dyn DynLanguageModel<C>> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EchoModel, ScriptedModel};
+
+    #[tokio::test]
+    async fn test_boxed_models_through_facade() {
+        let models: Vec<Box<dyn DynLanguageModel<()>>> = vec![
+            Box::new(EchoModel::new()),
+            Box::new(ScriptedModel::new().then_text("scripted")),
+        ];
+
+        let mut answers = Vec::new();
+        for model in &models {
+            let output = model
+                .execute_dyn(&(), LanguageInput::new("echoed"))
+                .await
+                .unwrap();
+            answers.push(output.text);
+        }
+        assert_eq!(answers, ["echoed", "scripted"]);
+
+        // The scripted model is exhausted; its error comes back boxed
+        let err = models[1]
+            .execute_dyn(&(), LanguageInput::new("again"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Scripted model has no more replies");
+    }
+
+    #[tokio::test]
+    async fn test_boxed_model_is_a_language_model() {
+        let model: Box<dyn DynLanguageModel<()>> = Box::new(EchoModel::new());
+        let model = model.with_system_prompt("be brief");
+
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hello");
+    }
+}
@@ -0,0 +1,333 @@
+//! Provider failover for models
+
+use crate::{
+    BoxError, BudgetError, CapabilityError, LanguageModel, Model, ModeratedError,
+    ScriptedModelError,
+};
+
+/// Classifies whether an error should trigger failover to another model.
+///
+/// Errors such as outages, rate limits or exhausted quotas are worth
+/// retrying elsewhere; errors caused by the request itself (a blocked
+/// prompt, an invalid input) would fail the same way on any provider.
+pub trait Failover {
+    fn should_failover(&self) -> bool;
+}
+
+impl Failover for std::convert::Infallible {
+    fn should_failover(&self) -> bool {
+        match *self {}
+    }
+}
+
+impl Failover for ScriptedModelError {
+    fn should_failover(&self) -> bool {
+        match self {
+            Self::Exhausted => true,
+        }
+    }
+}
+
+impl<E: Failover> Failover for ModeratedError<E> {
+    fn should_failover(&self) -> bool {
+        match self {
+            // Another provider would see the same prompt
+            Self::InputBlocked(_) | Self::OutputBlocked(_) => false,
+            Self::Model(err) => err.should_failover(),
+        }
+    }
+}
+
+impl<E: Failover> Failover for BudgetError<E> {
+    fn should_failover(&self) -> bool {
+        match self {
+            // Another provider has its own quota
+            Self::BudgetExceeded(_) => true,
+            Self::Model(err) => err.should_failover(),
+        }
+    }
+}
+
+impl Failover for CapabilityError {
+    fn should_failover(&self) -> bool {
+        match self {
+            Self::NotSupported(_) => true,
+        }
+    }
+}
+
+/// Type-erased errors cannot be classified, so they always fail over
+impl Failover for BoxError {
+    fn should_failover(&self) -> bool {
+        true
+    }
+}
+
+/// Fallback model error types
+#[derive(Debug)]
+pub enum FallbackError<E1, E2> {
+    /// The primary model failed with an error not worth failing over for
+    Primary(E1),
+    /// Both the primary and the fallback model failed
+    Exhausted { primary: E1, fallback: E2 },
+}
+
+impl<E1, E2> std::fmt::Display for FallbackError<E1, E2>
+where
+    E1: std::fmt::Display,
+    E2: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary(err) => write!(f, "Model error: {}", err),
+            Self::Exhausted { primary, fallback } => write!(
+                f,
+                "All models failed: primary: {}; fallback: {}",
+                primary, fallback
+            ),
+        }
+    }
+}
+
+impl<E1, E2> std::error::Error for FallbackError<E1, E2>
+where
+    E1: std::fmt::Debug + std::fmt::Display,
+    E2: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+impl<E1, E2: Failover> Failover for FallbackError<E1, E2> {
+    fn should_failover(&self) -> bool {
+        match self {
+            Self::Primary(_) => false,
+            Self::Exhausted { fallback, .. } => fallback.should_failover(),
+        }
+    }
+}
+
+/// Model that switches to a fallback provider when the primary fails.
+///
+/// Unlike retrying, the failed request is sent to a *different* model.
+/// Only errors for which `Failover::should_failover` returns true trigger
+/// the switch. Longer chains are built by nesting, either directly or via
+/// [`FallbackModel::or`]:
+///
+/// ```rust,ignore
+/// let model = FallbackModel::new(primary, secondary).or(local);
+/// ```
+pub struct FallbackModel<M1, M2> {
+    primary: M1,
+    fallback: M2,
+}
+
+impl<M1, M2> FallbackModel<M1, M2> {
+    pub fn new(primary: M1, fallback: M2) -> Self {
+        Self { primary, fallback }
+    }
+
+    /// Append another model to try after every model in this chain
+    pub fn or<M3>(self, next: M3) -> FallbackModel<Self, M3> {
+        FallbackModel::new(self, next)
+    }
+
+    /// Get a reference to the primary model
+    pub fn primary(&self) -> &M1 {
+        &self.primary
+    }
+
+    /// Get a reference to the fallback model
+    pub fn fallback(&self) -> &M2 {
+        &self.fallback
+    }
+}
+
+impl<M1, M2> Model for FallbackModel<M1, M2>
+where
+    M1: Model + Sync,
+    M1::Context: Sync,
+    M1::Input: Clone + Send,
+    M1::Error: Failover + Send,
+    M2: Model<Context = M1::Context, Input = M1::Input, Output = M1::Output> + Sync,
+{
+    type Context = M1::Context;
+    type Input = M1::Input;
+    type Output = M1::Output;
+    type Error = FallbackError<M1::Error, M2::Error>;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        let primary = match self.primary.execute(context, input.clone()).await {
+            Ok(output) => return Ok(output),
+            Err(err) if err.should_failover() => err,
+            Err(err) => return Err(FallbackError::Primary(err)),
+        };
+
+        self.fallback
+            .execute(context, input)
+            .await
+            .map_err(|fallback| FallbackError::Exhausted { primary, fallback })
+    }
+}
+
+impl<M1, M2> LanguageModel for FallbackModel<M1, M2>
+where
+    M1: LanguageModel + Sync,
+    M1::Context: Sync,
+    M1::Error: Failover + Send,
+    M2: LanguageModel<Context = M1::Context> + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        BudgetGuard, DynLanguageModel, EchoModel, LanguageInput, LanguageOutput, ScriptedModel,
+        UnsupportedLanguage,
+    };
+
+    #[derive(Debug, PartialEq)]
+    enum ProviderError {
+        Unavailable,
+        BadRequest,
+    }
+
+    impl Failover for ProviderError {
+        fn should_failover(&self) -> bool {
+            matches!(self, Self::Unavailable)
+        }
+    }
+
+    /// Model that always fails with the given error
+    struct FailingModel(fn() -> ProviderError);
+
+    impl Model for FailingModel {
+        type Context = ();
+        type Input = LanguageInput;
+        type Output = LanguageOutput;
+        type Error = ProviderError;
+
+        async fn execute<'a>(
+            &'a self,
+            _context: &'a (),
+            _input: LanguageInput,
+        ) -> Result<LanguageOutput, ProviderError> {
+            Err((self.0)())
+        }
+    }
+
+    impl LanguageModel for FailingModel {}
+
+    #[tokio::test]
+    async fn test_fallback_answers_when_primary_is_down() {
+        let model = FallbackModel::new(
+            FailingModel(|| ProviderError::Unavailable),
+            EchoModel::new(),
+        );
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hello");
+    }
+
+    #[tokio::test]
+    async fn test_fallback_skips_non_failover_errors() {
+        let model =
+            FallbackModel::new(FailingModel(|| ProviderError::BadRequest), EchoModel::new());
+        let err = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FallbackError::Primary(ProviderError::BadRequest)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_chain() {
+        let model = FallbackModel::new(
+            FailingModel(|| ProviderError::Unavailable),
+            ScriptedModel::new(),
+        )
+        .or(ScriptedModel::new().then_text("from the third model"));
+
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "from the third model");
+        assert_eq!(model.fallback().remaining(), 0);
+
+        // Every model in the chain has now failed
+        let err = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FallbackError::Exhausted {
+                primary: FallbackError::Exhausted { .. },
+                fallback: ScriptedModelError::Exhausted,
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_when_over_budget() {
+        let model = FallbackModel::new(
+            BudgetGuard::new(ScriptedModel::new().then_text("from the primary")).with_max_tokens(1),
+            EchoModel::new(),
+        );
+
+        // The first call crosses the budget, the second is refused and
+        // served by the fallback instead
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "from the primary");
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hello");
+        assert!(model.primary().is_exceeded());
+
+        // Model errors inside the guard are classified by the model
+        let model = FallbackModel::new(
+            BudgetGuard::new(FailingModel(|| ProviderError::BadRequest)),
+            EchoModel::new(),
+        );
+        let err = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FallbackError::Primary(BudgetError::Model(ProviderError::BadRequest))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_from_unsupported_and_boxed_models() {
+        let model = FallbackModel::new(UnsupportedLanguage::new(), EchoModel::new());
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hello");
+
+        let boxed: Box<dyn DynLanguageModel<()>> = Box::new(ScriptedModel::new());
+        let model = FallbackModel::new(boxed, EchoModel::new());
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hello");
+    }
+}
@@ -29,6 +29,44 @@
 
 use std::future::Future;
 
+mod budget;
+pub use budget::{BudgetError, BudgetGuard, Pricing};
+
+#[cfg(feature = "audio-convert")]
+mod audio;
+#[cfg(feature = "audio-convert")]
+pub use audio::{convert_audio, pcm_to_wav, wav_to_pcm, AudioError, PcmSpec};
+
+mod dynamic;
+pub use dynamic::{BoxError, DynLanguageModel};
+
+mod fallback;
+pub use fallback::{Failover, FallbackError, FallbackModel};
+
+mod local;
+pub use local::{EchoModel, ScriptedModel, ScriptedModelError, ScriptedReply};
+
+mod moderation;
+pub use moderation::{Moderated, ModeratedError, ModerationError, Moderator};
+
+mod partial;
+pub use partial::{
+    Capability, CapabilityError, CapabilityInput, PartialProvider, Unsupported,
+    UnsupportedEmbedding, UnsupportedImage, UnsupportedLanguage, UnsupportedSpeech,
+    UnsupportedVideo,
+};
+
+mod streaming;
+pub use streaming::{
+    collect_stream, token_channel, AudioChunk, ChannelTokenSink, ChannelTokenStream, StreamChunk,
+    StreamClosed, StreamingLanguageModel, StreamingSpeechModel,
+};
+
+#[cfg(feature = "record-replay")]
+mod record;
+#[cfg(feature = "record-replay")]
+pub use record::{Cassette, CassetteError, Interaction, RecordReplay, RecordReplayError};
+
 /// Core model trait - all AI models implement this
 pub trait Model {
     /// Context provided to the model (e.g., configuration, state)
@@ -53,6 +91,7 @@ pub trait Model {
 
 /// Language model input
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LanguageInput {
     pub prompt: String,
     pub system_prompt: Option<String>,
@@ -73,14 +112,30 @@ impl LanguageInput {
 
 /// Language model output
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LanguageOutput {
     pub text: String,
+    /// Tools the model asked to call (set when `finish_reason` is `ToolCalls`)
+    pub tool_calls: Vec<ToolCall>,
     pub finish_reason: FinishReason,
     pub usage: TokenUsage,
 }
 
+/// A tool invocation requested by a language model
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ToolCall {
+    /// Identifier used to match the tool result to this call
+    pub id: String,
+    /// Name of the tool to call
+    pub name: String,
+    /// Tool arguments, typically JSON-encoded
+    pub arguments: String,
+}
+
 /// Reason why model generation finished
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FinishReason {
     Stop,
     Length,
@@ -89,13 +144,32 @@ pub enum FinishReason {
 }
 
 /// Token usage information
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TokenUsage {
     pub prompt_tokens: usize,
     pub completion_tokens: usize,
     pub total_tokens: usize,
 }
 
+impl std::ops::Add for TokenUsage {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            prompt_tokens: self.prompt_tokens + other.prompt_tokens,
+            completion_tokens: self.completion_tokens + other.completion_tokens,
+            total_tokens: self.total_tokens + other.total_tokens,
+        }
+    }
+}
+
+impl std::ops::AddAssign for TokenUsage {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
 /// Language model specialization
 pub trait LanguageModel: Model<Input = LanguageInput, Output = LanguageOutput> {
     /// Create a new instance with a system prompt
@@ -108,6 +182,14 @@ pub trait LanguageModel: Model<Input = LanguageInput, Output = LanguageOutput> {
             system_prompt: prompt.into(),
         }
     }
+
+    /// Run a content moderation policy before and after each call
+    fn with_moderation<D: Moderator>(self, moderator: D) -> Moderated<Self, D>
+    where
+        Self: Sized,
+    {
+        Moderated::new(self, moderator)
+    }
 }
 
 /// Wrapper that adds a system prompt to a language model
@@ -205,16 +287,94 @@ pub enum AudioInput {
     /// Text to synthesize into speech
     TextToSpeech { text: String, voice: Option<String> },
     /// Audio to transcribe into text
-    SpeechToText { audio: Vec<u8> },
+    SpeechToText {
+        audio: Vec<u8>,
+        /// Samples per second, required to interpret raw PCM
+        sample_rate: Option<u32>,
+        /// Number of interleaved channels, required to interpret raw PCM
+        channels: Option<u16>,
+    },
+}
+
+impl AudioInput {
+    pub fn text_to_speech(text: impl Into<String>) -> Self {
+        Self::TextToSpeech {
+            text: text.into(),
+            voice: None,
+        }
+    }
+
+    pub fn speech_to_text(audio: Vec<u8>) -> Self {
+        Self::SpeechToText {
+            audio,
+            sample_rate: None,
+            channels: None,
+        }
+    }
+
+    /// Set the sample rate of speech-to-text audio; ignored for text-to-speech
+    pub fn with_sample_rate(mut self, rate: u32) -> Self {
+        if let Self::SpeechToText { sample_rate, .. } = &mut self {
+            *sample_rate = Some(rate);
+        }
+        self
+    }
+
+    /// Set the channel count of speech-to-text audio; ignored for text-to-speech
+    pub fn with_channels(mut self, count: u16) -> Self {
+        if let Self::SpeechToText { channels, .. } = &mut self {
+            *channels = Some(count);
+        }
+        self
+    }
 }
 
 /// Audio/Speech output
 #[derive(Debug, Clone)]
 pub enum AudioOutput {
     /// Synthesized speech audio
-    Audio { data: Vec<u8>, format: AudioFormat },
+    Audio {
+        data: Vec<u8>,
+        format: AudioFormat,
+        sample_rate: Option<u32>,
+        channels: Option<u16>,
+    },
     /// Transcribed text
     Text { text: String },
+    /// Transcribed text with timing information, for providers that
+    /// report segment or word timestamps
+    Transcript {
+        text: String,
+        segments: Vec<TranscriptSegment>,
+    },
+}
+
+impl AudioOutput {
+    /// Transcribed text, if this is a transcription
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            Self::Text { text } | Self::Transcript { text, .. } => Some(text),
+            Self::Audio { .. } => None,
+        }
+    }
+
+    /// Timed segments of a transcription; empty when none were provided
+    pub fn segments(&self) -> &[TranscriptSegment] {
+        match self {
+            Self::Transcript { segments, .. } => segments,
+            _ => &[],
+        }
+    }
+}
+
+/// A timed span of transcribed speech
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptSegment {
+    pub text: String,
+    /// Offset from the start of the audio where the segment begins
+    pub start_seconds: f32,
+    /// Offset from the start of the audio where the segment ends
+    pub end_seconds: f32,
 }
 
 /// Audio format
@@ -223,6 +383,8 @@ pub enum AudioFormat {
     Wav,
     Mp3,
     Ogg,
+    /// Raw 16-bit little-endian PCM samples, without a header
+    Pcm,
 }
 
 /// Speech/Audio model
@@ -258,3 +420,70 @@ pub trait ModelProvider {
     fn speech_model(&self) -> &Self::SpeechModel;
     fn embedding_model(&self) -> &Self::EmbeddingModel;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audio_output_text_and_segments() {
+        let plain = AudioOutput::Text {
+            text: "hello world".to_string(),
+        };
+        assert_eq!(plain.text(), Some("hello world"));
+        assert!(plain.segments().is_empty());
+
+        let timed = AudioOutput::Transcript {
+            text: "hello world".to_string(),
+            segments: vec![
+                TranscriptSegment {
+                    text: "hello".to_string(),
+                    start_seconds: 0.0,
+                    end_seconds: 0.4,
+                },
+                TranscriptSegment {
+                    text: "world".to_string(),
+                    start_seconds: 0.5,
+                    end_seconds: 0.9,
+                },
+            ],
+        };
+        assert_eq!(timed.text(), Some("hello world"));
+        assert_eq!(timed.segments().len(), 2);
+        assert_eq!(timed.segments()[1].start_seconds, 0.5);
+
+        let audio = AudioOutput::Audio {
+            data: vec![],
+            format: AudioFormat::Wav,
+            sample_rate: Some(24_000),
+            channels: Some(1),
+        };
+        assert_eq!(audio.text(), None);
+    }
+
+    #[test]
+    fn test_audio_input_builders() {
+        let input = AudioInput::speech_to_text(vec![0; 4]);
+        assert!(matches!(
+            input,
+            AudioInput::SpeechToText {
+                sample_rate: None,
+                channels: None,
+                ..
+            }
+        ));
+
+        let input = input.with_sample_rate(16_000).with_channels(2);
+        assert!(matches!(
+            input,
+            AudioInput::SpeechToText {
+                sample_rate: Some(16_000),
+                channels: Some(2),
+                ..
+            }
+        ));
+
+        let tts = AudioInput::text_to_speech("hi").with_sample_rate(16_000);
+        assert!(matches!(tts, AudioInput::TextToSpeech { voice: None, .. }));
+    }
+}
@@ -0,0 +1,244 @@
+//! In-process language models for offline testing and examples
+
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::{
+    FinishReason, LanguageInput, LanguageModel, LanguageOutput, Model, TokenUsage, ToolCall,
+};
+
+/// Rough usage estimate for local models, counting whitespace-separated words
+fn estimate_usage(prompt: &str, completion: &str) -> TokenUsage {
+    let prompt_tokens = prompt.split_whitespace().count();
+    let completion_tokens = completion.split_whitespace().count();
+    TokenUsage {
+        prompt_tokens,
+        completion_tokens,
+        total_tokens: prompt_tokens + completion_tokens,
+    }
+}
+
+/// Language model that answers with the prompt it was given.
+///
+/// Works with any context type, so it can stand in for a real provider
+/// without an API key.
+pub struct EchoModel<C = ()> {
+    _context: PhantomData<fn(&C)>,
+}
+
+impl<C> EchoModel<C> {
+    pub fn new() -> Self {
+        Self {
+            _context: PhantomData,
+        }
+    }
+}
+
+impl<C> Default for EchoModel<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Sync> Model for EchoModel<C> {
+    type Context = C;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = std::convert::Infallible;
+
+    async fn execute<'a>(
+        &'a self,
+        _context: &'a C,
+        input: LanguageInput,
+    ) -> Result<LanguageOutput, Self::Error> {
+        Ok(LanguageOutput {
+            usage: estimate_usage(&input.prompt, &input.prompt),
+            text: input.prompt,
+            tool_calls: Vec::new(),
+            finish_reason: FinishReason::Stop,
+        })
+    }
+}
+
+impl<C: Sync> LanguageModel for EchoModel<C> {}
+
+/// A canned reply returned by `ScriptedModel`
+#[derive(Debug, Clone)]
+pub enum ScriptedReply {
+    /// Final text answer
+    Text(String),
+    /// Request to call a tool
+    ToolCall { name: String, arguments: String },
+}
+
+/// Scripted model error types
+#[derive(Debug)]
+pub enum ScriptedModelError {
+    /// The model was called more times than it has replies
+    Exhausted,
+}
+
+impl std::fmt::Display for ScriptedModelError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Exhausted => write!(f, "Scripted model has no more replies"),
+        }
+    }
+}
+
+impl std::error::Error for ScriptedModelError {}
+
+/// Language model that plays back a fixed sequence of replies.
+///
+/// Each call returns the next reply in order, so tool-call-then-answer
+/// sequences can be exercised deterministically.
+///
+/// ```rust,ignore
+/// let model = ScriptedModel::<()>::new()
+///     .then_tool_call("calculator", r#"{"expr":"2+2"}"#)
+///     .then_text("2+2 is 4");
+/// ```
+pub struct ScriptedModel<C = ()> {
+    replies: Mutex<VecDeque<ScriptedReply>>,
+    calls: AtomicUsize,
+    _context: PhantomData<fn(&C)>,
+}
+
+impl<C> ScriptedModel<C> {
+    pub fn new() -> Self {
+        Self {
+            replies: Mutex::new(VecDeque::new()),
+            calls: AtomicUsize::new(0),
+            _context: PhantomData,
+        }
+    }
+
+    /// Queue a final text answer
+    pub fn then_text(self, text: impl Into<String>) -> Self {
+        self.then(ScriptedReply::Text(text.into()))
+    }
+
+    /// Queue a tool call
+    pub fn then_tool_call(self, name: impl Into<String>, arguments: impl Into<String>) -> Self {
+        self.then(ScriptedReply::ToolCall {
+            name: name.into(),
+            arguments: arguments.into(),
+        })
+    }
+
+    /// Queue an arbitrary reply
+    pub fn then(self, reply: ScriptedReply) -> Self {
+        self.replies.lock().unwrap().push_back(reply);
+        self
+    }
+
+    /// Number of replies not yet played back
+    pub fn remaining(&self) -> usize {
+        self.replies.lock().unwrap().len()
+    }
+}
+
+impl<C> Default for ScriptedModel<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Sync> Model for ScriptedModel<C> {
+    type Context = C;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = ScriptedModelError;
+
+    async fn execute<'a>(
+        &'a self,
+        _context: &'a C,
+        input: LanguageInput,
+    ) -> Result<LanguageOutput, ScriptedModelError> {
+        let reply = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or(ScriptedModelError::Exhausted)?;
+        let call_index = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+
+        let output = match reply {
+            ScriptedReply::Text(text) => LanguageOutput {
+                usage: estimate_usage(&input.prompt, &text),
+                text,
+                tool_calls: Vec::new(),
+                finish_reason: FinishReason::Stop,
+            },
+            ScriptedReply::ToolCall { name, arguments } => LanguageOutput {
+                usage: estimate_usage(&input.prompt, &arguments),
+                text: String::new(),
+                tool_calls: vec![ToolCall {
+                    id: format!("call_{}", call_index),
+                    name,
+                    arguments,
+                }],
+                finish_reason: FinishReason::ToolCalls,
+            },
+        };
+        Ok(output)
+    }
+}
+
+impl<C: Sync> LanguageModel for ScriptedModel<C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_echo_model() {
+        let model = EchoModel::new();
+        let output = model
+            .execute(&(), LanguageInput::new("hello there"))
+            .await
+            .unwrap();
+
+        assert_eq!(output.text, "hello there");
+        assert_eq!(output.finish_reason, FinishReason::Stop);
+        assert_eq!(output.usage.prompt_tokens, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scripted_tool_call_then_answer() {
+        let model = ScriptedModel::new()
+            .then_tool_call("calculator", r#"{"expr":"2+2"}"#)
+            .then_text("2+2 is 4");
+
+        let first = model
+            .execute(&(), LanguageInput::new("What is 2+2?"))
+            .await
+            .unwrap();
+        assert_eq!(first.finish_reason, FinishReason::ToolCalls);
+        assert_eq!(
+            first.tool_calls,
+            vec![ToolCall {
+                id: "call_1".to_string(),
+                name: "calculator".to_string(),
+                arguments: r#"{"expr":"2+2"}"#.to_string(),
+            }]
+        );
+
+        let second = model
+            .execute(&(), LanguageInput::new("calculator returned 4"))
+            .await
+            .unwrap();
+        assert_eq!(second.finish_reason, FinishReason::Stop);
+        assert_eq!(second.text, "2+2 is 4");
+        assert!(second.tool_calls.is_empty());
+
+        assert_eq!(model.remaining(), 0);
+        let err = model
+            .execute(&(), LanguageInput::new("again"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ScriptedModelError::Exhausted));
+    }
+}
@@ -0,0 +1,186 @@
+//! Content moderation for language models
+
+use std::future::Future;
+
+use crate::{LanguageInput, LanguageModel, LanguageOutput, Model};
+
+/// Reason a prompt or response was blocked by a `Moderator`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModerationError {
+    pub reason: String,
+}
+
+impl ModerationError {
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ModerationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Content blocked: {}", self.reason)
+    }
+}
+
+impl std::error::Error for ModerationError {}
+
+/// Content moderation policy applied around model execution.
+///
+/// Both checks allow everything by default, so a policy only needs to
+/// implement the side it cares about. Checks are async so they can call
+/// out to an external moderation service.
+pub trait Moderator {
+    /// Check a prompt before it is sent to the model
+    fn moderate_input<'a>(
+        &'a self,
+        _input: &'a LanguageInput,
+    ) -> impl Future<Output = Result<(), ModerationError>> + Send + 'a {
+        async { Ok(()) }
+    }
+
+    /// Check a model response before it is returned to the caller
+    fn moderate_output<'a>(
+        &'a self,
+        _output: &'a LanguageOutput,
+    ) -> impl Future<Output = Result<(), ModerationError>> + Send + 'a {
+        async { Ok(()) }
+    }
+}
+
+/// Moderated model error types
+#[derive(Debug)]
+pub enum ModeratedError<E> {
+    /// The prompt was blocked; the model was not called
+    InputBlocked(ModerationError),
+    /// The model response was blocked
+    OutputBlocked(ModerationError),
+    /// The wrapped model failed
+    Model(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ModeratedError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InputBlocked(err) => write!(f, "Prompt rejected: {}", err),
+            Self::OutputBlocked(err) => write!(f, "Response rejected: {}", err),
+            Self::Model(err) => write!(f, "Model error: {}", err),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for ModeratedError<E> {}
+
+/// Wrapper that runs a `Moderator` before and after a language model
+pub struct Moderated<M, D> {
+    inner: M,
+    moderator: D,
+}
+
+impl<M, D> Moderated<M, D> {
+    pub fn new(inner: M, moderator: D) -> Self {
+        Self { inner, moderator }
+    }
+}
+
+impl<M, D> Model for Moderated<M, D>
+where
+    M: LanguageModel + Sync,
+    M::Context: Sync,
+    D: Moderator + Sync,
+{
+    type Context = M::Context;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = ModeratedError<M::Error>;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        self.moderator
+            .moderate_input(&input)
+            .await
+            .map_err(ModeratedError::InputBlocked)?;
+
+        let output = self
+            .inner
+            .execute(context, input)
+            .await
+            .map_err(ModeratedError::Model)?;
+
+        self.moderator
+            .moderate_output(&output)
+            .await
+            .map_err(ModeratedError::OutputBlocked)?;
+        Ok(output)
+    }
+}
+
+impl<M, D> LanguageModel for Moderated<M, D>
+where
+    M: LanguageModel + Sync,
+    M::Context: Sync,
+    D: Moderator + Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EchoModel;
+
+    /// Blocks prompts mentioning "forbidden" and responses mentioning "secret"
+    struct KeywordPolicy;
+
+    impl Moderator for KeywordPolicy {
+        async fn moderate_input(&self, input: &LanguageInput) -> Result<(), ModerationError> {
+            if input.prompt.contains("forbidden") {
+                return Err(ModerationError::new("forbidden topic"));
+            }
+            Ok(())
+        }
+
+        async fn moderate_output(&self, output: &LanguageOutput) -> Result<(), ModerationError> {
+            if output.text.contains("secret") {
+                return Err(ModerationError::new("leaks a secret"));
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_moderation_allows_clean_content() {
+        let model = EchoModel::new().with_moderation(KeywordPolicy);
+        let output = model
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hello");
+    }
+
+    #[tokio::test]
+    async fn test_moderation_blocks_prompt() {
+        let model = EchoModel::new().with_moderation(KeywordPolicy);
+        let err = model
+            .execute(&(), LanguageInput::new("a forbidden question"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ModeratedError::InputBlocked(reason) if reason.reason == "forbidden topic")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_moderation_blocks_response() {
+        // The echo model repeats the prompt, so the response contains "secret"
+        let model = EchoModel::new().with_moderation(KeywordPolicy);
+        let err = model
+            .execute(&(), LanguageInput::new("tell me the secret"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ModeratedError::OutputBlocked(_)));
+    }
+}
@@ -0,0 +1,318 @@
+//! Providers that only offer some model capabilities
+
+use std::marker::PhantomData;
+
+use crate::{
+    AudioInput, AudioOutput, EmbeddingInput, EmbeddingModel, Image, ImageGenModel, ImagePrompt,
+    LanguageInput, LanguageModel, LanguageOutput, Model, ModelProvider, SpeechModel, Vector, Video,
+    VideoGenModel, VideoPrompt,
+};
+
+/// A model capability offered by a `ModelProvider`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    Language,
+    Image,
+    Video,
+    Speech,
+    Embedding,
+}
+
+impl std::fmt::Display for Capability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Language => "language",
+            Self::Image => "image generation",
+            Self::Video => "video generation",
+            Self::Speech => "speech",
+            Self::Embedding => "embedding",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Model input types, mapped to the capability they belong to
+pub trait CapabilityInput {
+    const CAPABILITY: Capability;
+}
+
+impl CapabilityInput for LanguageInput {
+    const CAPABILITY: Capability = Capability::Language;
+}
+
+impl CapabilityInput for ImagePrompt {
+    const CAPABILITY: Capability = Capability::Image;
+}
+
+impl CapabilityInput for VideoPrompt {
+    const CAPABILITY: Capability = Capability::Video;
+}
+
+impl CapabilityInput for AudioInput {
+    const CAPABILITY: Capability = Capability::Speech;
+}
+
+impl CapabilityInput for EmbeddingInput {
+    const CAPABILITY: Capability = Capability::Embedding;
+}
+
+/// Capability error types
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapabilityError {
+    /// The provider does not offer this capability
+    NotSupported(Capability),
+}
+
+impl std::fmt::Display for CapabilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotSupported(capability) => {
+                write!(
+                    f,
+                    "{} models are not supported by this provider",
+                    capability
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CapabilityError {}
+
+/// Placeholder for a capability a provider does not offer.
+///
+/// Implements the model trait for its input/output pair, failing every
+/// call with `CapabilityError::NotSupported`.
+pub struct Unsupported<I, O, C = ()> {
+    _types: PhantomData<fn(&C, I) -> O>,
+}
+
+impl<I, O, C> Unsupported<I, O, C> {
+    pub fn new() -> Self {
+        Self {
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<I, O, C> Default for Unsupported<I, O, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, O, C> Model for Unsupported<I, O, C>
+where
+    I: CapabilityInput + Send,
+    O: Send,
+    C: Sync,
+{
+    type Context = C;
+    type Input = I;
+    type Output = O;
+    type Error = CapabilityError;
+
+    async fn execute<'a>(&'a self, _context: &'a C, _input: I) -> Result<O, CapabilityError> {
+        Err(CapabilityError::NotSupported(I::CAPABILITY))
+    }
+}
+
+pub type UnsupportedLanguage = Unsupported<LanguageInput, LanguageOutput>;
+pub type UnsupportedImage = Unsupported<ImagePrompt, Image>;
+pub type UnsupportedVideo = Unsupported<VideoPrompt, Video>;
+pub type UnsupportedSpeech = Unsupported<AudioInput, AudioOutput>;
+pub type UnsupportedEmbedding = Unsupported<EmbeddingInput, Vector>;
+
+impl<C: Sync> LanguageModel for Unsupported<LanguageInput, LanguageOutput, C> {}
+impl<C: Sync> ImageGenModel for Unsupported<ImagePrompt, Image, C> {}
+impl<C: Sync> VideoGenModel for Unsupported<VideoPrompt, Video, C> {}
+impl<C: Sync> SpeechModel for Unsupported<AudioInput, AudioOutput, C> {}
+impl<C: Sync> EmbeddingModel for Unsupported<EmbeddingInput, Vector, C> {}
+
+/// `ModelProvider` assembled from the capabilities a provider offers.
+///
+/// Every capability starts out `Unsupported`; the `with_*` builders
+/// replace it with a real model.
+///
+/// ```rust,ignore
+/// let provider = PartialProvider::new()
+///     .with_language(chat_model)
+///     .with_embedding(embedding_model);
+/// ```
+pub struct PartialProvider<
+    L = UnsupportedLanguage,
+    I = UnsupportedImage,
+    V = UnsupportedVideo,
+    S = UnsupportedSpeech,
+    E = UnsupportedEmbedding,
+> {
+    language: L,
+    image: I,
+    video: V,
+    speech: S,
+    embedding: E,
+}
+
+impl PartialProvider {
+    /// A provider with no supported capabilities
+    pub fn new() -> Self {
+        Self {
+            language: Unsupported::new(),
+            image: Unsupported::new(),
+            video: Unsupported::new(),
+            speech: Unsupported::new(),
+            embedding: Unsupported::new(),
+        }
+    }
+}
+
+impl Default for PartialProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L, I, V, S, E> PartialProvider<L, I, V, S, E> {
+    /// Provide language models
+    pub fn with_language<M: LanguageModel>(self, model: M) -> PartialProvider<M, I, V, S, E> {
+        PartialProvider {
+            language: model,
+            image: self.image,
+            video: self.video,
+            speech: self.speech,
+            embedding: self.embedding,
+        }
+    }
+
+    /// Provide image generation models
+    pub fn with_image<M: ImageGenModel>(self, model: M) -> PartialProvider<L, M, V, S, E> {
+        PartialProvider {
+            language: self.language,
+            image: model,
+            video: self.video,
+            speech: self.speech,
+            embedding: self.embedding,
+        }
+    }
+
+    /// Provide video generation models
+    pub fn with_video<M: VideoGenModel>(self, model: M) -> PartialProvider<L, I, M, S, E> {
+        PartialProvider {
+            language: self.language,
+            image: self.image,
+            video: model,
+            speech: self.speech,
+            embedding: self.embedding,
+        }
+    }
+
+    /// Provide speech models
+    pub fn with_speech<M: SpeechModel>(self, model: M) -> PartialProvider<L, I, V, M, E> {
+        PartialProvider {
+            language: self.language,
+            image: self.image,
+            video: self.video,
+            speech: model,
+            embedding: self.embedding,
+        }
+    }
+
+    /// Provide embedding models
+    pub fn with_embedding<M: EmbeddingModel>(self, model: M) -> PartialProvider<L, I, V, S, M> {
+        PartialProvider {
+            language: self.language,
+            image: self.image,
+            video: self.video,
+            speech: self.speech,
+            embedding: model,
+        }
+    }
+}
+
+impl<L, I, V, S, E> ModelProvider for PartialProvider<L, I, V, S, E>
+where
+    L: LanguageModel,
+    I: ImageGenModel,
+    V: VideoGenModel,
+    S: SpeechModel,
+    E: EmbeddingModel,
+{
+    type LanguageModel = L;
+    type ImageModel = I;
+    type VideoModel = V;
+    type SpeechModel = S;
+    type EmbeddingModel = E;
+
+    fn language_model(&self) -> &L {
+        &self.language
+    }
+
+    fn image_model(&self) -> &I {
+        &self.image
+    }
+
+    fn video_model(&self) -> &V {
+        &self.video
+    }
+
+    fn speech_model(&self) -> &S {
+        &self.speech
+    }
+
+    fn embedding_model(&self) -> &E {
+        &self.embedding
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EchoModel;
+
+    #[tokio::test]
+    async fn test_text_only_provider() {
+        let provider = PartialProvider::new().with_language(EchoModel::new());
+
+        let output = provider
+            .language_model()
+            .execute(&(), LanguageInput::new("hi"))
+            .await
+            .unwrap();
+        assert_eq!(output.text, "hi");
+
+        let err = provider
+            .embedding_model()
+            .execute(
+                &(),
+                EmbeddingInput {
+                    text: "hi".to_string(),
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, CapabilityError::NotSupported(Capability::Embedding));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_image_model() {
+        let provider = PartialProvider::new();
+        let err = provider
+            .image_model()
+            .execute(
+                &(),
+                ImagePrompt {
+                    prompt: "a cat".to_string(),
+                    negative_prompt: None,
+                    width: None,
+                    height: None,
+                    steps: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "image generation models are not supported by this provider"
+        );
+    }
+}
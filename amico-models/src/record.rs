@@ -0,0 +1,322 @@
+//! Record/replay wrapper for deterministic model tests (requires the
+//! `record-replay` feature)
+
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{LanguageInput, LanguageModel, LanguageOutput, Model};
+
+/// A single recorded model call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Hash of the serialized input, used to look the call up on replay
+    pub key: String,
+    pub input: LanguageInput,
+    pub output: LanguageOutput,
+}
+
+/// A recorded sequence of model calls, stored as JSON
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<Interaction>,
+}
+
+/// Cassette error types
+#[derive(Debug)]
+pub enum CassetteError {
+    /// The cassette file could not be read or written
+    Io(std::io::Error),
+    /// The cassette or an input could not be (de)serialized
+    Format(serde_json::Error),
+}
+
+impl std::fmt::Display for CassetteError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Cassette I/O error: {}", err),
+            Self::Format(err) => write!(f, "Cassette format error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for CassetteError {}
+
+impl From<std::io::Error> for CassetteError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CassetteError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Format(err)
+    }
+}
+
+impl Cassette {
+    /// Load a cassette from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let data = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Write the cassette to a JSON file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CassetteError> {
+        let data = serde_json::to_vec_pretty(self)?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Compute the lookup key for an input.
+    ///
+    /// This is a 64-bit FNV-1a hash of the input's JSON encoding, so keys
+    /// stay stable across builds and platforms.
+    pub fn key(input: &LanguageInput) -> Result<String, CassetteError> {
+        let encoded = serde_json::to_vec(input)?;
+        let hash = encoded.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        });
+        Ok(format!("{:016x}", hash))
+    }
+}
+
+/// Record/replay error types
+#[derive(Debug)]
+pub enum RecordReplayError<E> {
+    /// The wrapped model failed (record mode only)
+    Model(E),
+    /// The cassette could not be read, written or encoded
+    Cassette(CassetteError),
+    /// No recorded interaction matches the input (replay mode only)
+    MissingInteraction(String),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for RecordReplayError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Model(err) => write!(f, "Model error: {}", err),
+            Self::Cassette(err) => write!(f, "{}", err),
+            Self::MissingInteraction(key) => {
+                write!(f, "No recorded interaction for input {}", key)
+            }
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for RecordReplayError<E> {}
+
+enum Mode {
+    /// Append each call to the cassette, writing it to `path` on flush
+    Record {
+        path: PathBuf,
+        cassette: Cassette,
+        /// Number of interactions already written to `path`
+        saved: usize,
+    },
+    /// Serve recorded outputs by input key, in recorded order
+    Replay {
+        outputs: HashMap<String, VecDeque<LanguageOutput>>,
+    },
+}
+
+/// Model wrapper implementing the VCR pattern for language models.
+///
+/// In record mode every call goes to the inner model and the
+/// `(input, output)` pair is appended to a cassette in memory. The
+/// cassette is written to its file by [`RecordReplay::flush`], or when the
+/// wrapper is dropped if there are unsaved interactions; call `flush` to
+/// see write errors, since `Drop` ignores them. In replay mode
+/// outputs are served from the cassette and the inner model is never
+/// called, so tests run without network access or API keys.
+pub struct RecordReplay<M> {
+    inner: M,
+    mode: Mutex<Mode>,
+}
+
+impl<M> RecordReplay<M> {
+    /// Wrap `inner`, recording every call to a new cassette at `path`
+    pub fn record(inner: M, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            mode: Mutex::new(Mode::Record {
+                path: path.into(),
+                cassette: Cassette::default(),
+                saved: 0,
+            }),
+        }
+    }
+
+    /// Wrap `inner`, serving responses from the cassette at `path`
+    pub fn replay(inner: M, path: impl AsRef<Path>) -> Result<Self, CassetteError> {
+        let cassette = Cassette::load(path)?;
+        let mut outputs: HashMap<String, VecDeque<LanguageOutput>> = HashMap::new();
+        for interaction in cassette.interactions {
+            outputs
+                .entry(interaction.key)
+                .or_default()
+                .push_back(interaction.output);
+        }
+
+        Ok(Self {
+            inner,
+            mode: Mutex::new(Mode::Replay { outputs }),
+        })
+    }
+
+    /// Get a reference to the wrapped model
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    /// Write recorded interactions to the cassette file.
+    ///
+    /// Does nothing in replay mode or when nothing new was recorded.
+    pub fn flush(&self) -> Result<(), CassetteError> {
+        flush(&mut self.mode.lock().unwrap())
+    }
+}
+
+impl<M> Drop for RecordReplay<M> {
+    fn drop(&mut self) {
+        if let Ok(mode) = self.mode.get_mut() {
+            let _ = flush(mode);
+        }
+    }
+}
+
+/// Save a recording cassette if it has unsaved interactions
+fn flush(mode: &mut Mode) -> Result<(), CassetteError> {
+    if let Mode::Record {
+        path,
+        cassette,
+        saved,
+    } = mode
+    {
+        if *saved < cassette.interactions.len() {
+            cassette.save(path)?;
+            *saved = cassette.interactions.len();
+        }
+    }
+    Ok(())
+}
+
+impl<M> Model for RecordReplay<M>
+where
+    M: LanguageModel + Sync,
+    M::Context: Sync,
+{
+    type Context = M::Context;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = RecordReplayError<M::Error>;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: LanguageInput,
+    ) -> Result<LanguageOutput, Self::Error> {
+        let key = Cassette::key(&input).map_err(RecordReplayError::Cassette)?;
+
+        if let Mode::Replay { outputs } = &mut *self.mode.lock().unwrap() {
+            return outputs
+                .get_mut(&key)
+                .and_then(|queue| queue.pop_front())
+                .ok_or(RecordReplayError::MissingInteraction(key));
+        }
+
+        let output = self
+            .inner
+            .execute(context, input.clone())
+            .await
+            .map_err(RecordReplayError::Model)?;
+
+        if let Mode::Record { cassette, .. } = &mut *self.mode.lock().unwrap() {
+            cassette.interactions.push(Interaction {
+                key,
+                input,
+                output: output.clone(),
+            });
+        }
+        Ok(output)
+    }
+}
+
+impl<M> LanguageModel for RecordReplay<M>
+where
+    M: LanguageModel + Sync,
+    M::Context: Sync,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FinishReason, ScriptedModel};
+
+    #[tokio::test]
+    async fn test_replay_reproduces_recording() {
+        let path = std::env::temp_dir().join(format!("amico-cassette-{}.json", std::process::id()));
+
+        let recorder = RecordReplay::record(
+            ScriptedModel::new()
+                .then_tool_call("search", r#"{"q":"rust"}"#)
+                .then_text("Rust is a language"),
+            &path,
+        );
+        let first = recorder
+            .execute(&(), LanguageInput::new("What is Rust?"))
+            .await
+            .unwrap();
+        let second = recorder
+            .execute(&(), LanguageInput::new("search returned docs"))
+            .await
+            .unwrap();
+        // Nothing is written until the recording is flushed
+        assert!(!path.exists());
+        recorder.flush().unwrap();
+
+        // The inner model has no replies, so any call to it would fail
+        let player = RecordReplay::replay(ScriptedModel::new(), &path).unwrap();
+        let replayed_second = player
+            .execute(&(), LanguageInput::new("search returned docs"))
+            .await
+            .unwrap();
+        let replayed_first = player
+            .execute(&(), LanguageInput::new("What is Rust?"))
+            .await
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayed_first.finish_reason, FinishReason::ToolCalls);
+        assert_eq!(replayed_first.tool_calls, first.tool_calls);
+        assert_eq!(replayed_second.text, second.text);
+        assert_eq!(player.inner().remaining(), 0);
+
+        let err = player
+            .execute(&(), LanguageInput::new("never recorded"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RecordReplayError::MissingInteraction(_)));
+    }
+
+    #[tokio::test]
+    async fn test_record_saves_on_drop() {
+        let path =
+            std::env::temp_dir().join(format!("amico-cassette-drop-{}.json", std::process::id()));
+
+        let recorder = RecordReplay::record(ScriptedModel::new().then_text("Hi"), &path);
+        recorder
+            .execute(&(), LanguageInput::new("Hello"))
+            .await
+            .unwrap();
+        drop(recorder);
+
+        let cassette = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(cassette.interactions.len(), 1);
+        assert_eq!(cassette.interactions[0].output.text, "Hi");
+    }
+}
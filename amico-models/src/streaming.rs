@@ -0,0 +1,337 @@
+//! Token streaming for language models and audio streaming for speech
+//! models
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc;
+use futures::{future, Stream, StreamExt};
+
+use crate::{
+    AudioFormat, AudioInput, FinishReason, LanguageInput, LanguageModel, LanguageOutput,
+    SpeechModel, TokenUsage, ToolCall,
+};
+
+/// An incremental piece of a streamed language model response
+#[derive(Debug, Clone, Default)]
+pub struct StreamChunk {
+    /// Text generated since the previous chunk
+    pub delta: String,
+    /// Tool calls completed in this chunk
+    pub tool_calls: Vec<ToolCall>,
+    /// Finish reason, usually only set on the final chunk
+    pub finish_reason: Option<FinishReason>,
+    /// Token usage, usually only set on the final chunk
+    pub usage: Option<TokenUsage>,
+    /// Whether this is the final chunk
+    pub done: bool,
+}
+
+impl StreamChunk {
+    /// A chunk carrying only a text delta
+    pub fn delta(text: impl Into<String>) -> Self {
+        Self {
+            delta: text.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Language model that can stream its response as it is generated
+pub trait StreamingLanguageModel: LanguageModel {
+    /// Stream of response chunks
+    type TokenStream: Stream<Item = Result<StreamChunk, Self::Error>> + Send;
+
+    /// Start generating a response, yielding chunks as they arrive
+    fn stream(&self, context: &Self::Context, input: LanguageInput) -> Self::TokenStream;
+}
+
+/// Collect a chunk stream into a complete `LanguageOutput`.
+///
+/// Deltas and tool calls are concatenated until a chunk with `done` is
+/// seen or the stream ends. If no chunk reports a finish reason, it is
+/// inferred: `ToolCalls` when tool calls were streamed, `Stop` otherwise.
+pub async fn collect_stream<S, E>(stream: S) -> Result<LanguageOutput, E>
+where
+    S: Stream<Item = Result<StreamChunk, E>>,
+{
+    let mut stream = std::pin::pin!(stream);
+    let mut text = String::new();
+    let mut tool_calls = Vec::new();
+    let mut finish_reason = None;
+    let mut usage = None;
+
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        text.push_str(&chunk.delta);
+        tool_calls.extend(chunk.tool_calls);
+        finish_reason = chunk.finish_reason.or(finish_reason);
+        usage = chunk.usage.or(usage);
+        if chunk.done {
+            break;
+        }
+    }
+
+    let finish_reason = finish_reason.unwrap_or(if tool_calls.is_empty() {
+        FinishReason::Stop
+    } else {
+        FinishReason::ToolCalls
+    });
+
+    Ok(LanguageOutput {
+        text,
+        tool_calls,
+        finish_reason,
+        usage: usage.unwrap_or_default(),
+    })
+}
+
+/// Error returned when sending into a channel whose stream was dropped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StreamClosed;
+
+impl std::fmt::Display for StreamClosed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Token stream was closed by the consumer")
+    }
+}
+
+impl std::error::Error for StreamClosed {}
+
+/// Create a bounded channel for streaming model responses.
+///
+/// The producer side (`ChannelTokenSink`) waits when `buffer` chunks are
+/// pending, so a slow consumer applies backpressure to the model instead
+/// of letting chunks pile up in memory. The consumer side
+/// (`ChannelTokenStream`) can be used directly as a
+/// `StreamingLanguageModel::TokenStream`.
+pub fn token_channel<E>(buffer: usize) -> (ChannelTokenSink<E>, ChannelTokenStream<E>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    (ChannelTokenSink { tx }, ChannelTokenStream { rx })
+}
+
+/// Producer half of a [`token_channel`]
+pub struct ChannelTokenSink<E> {
+    tx: mpsc::Sender<Result<StreamChunk, E>>,
+}
+
+impl<E> Clone for ChannelTokenSink<E> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<E> ChannelTokenSink<E> {
+    /// Send a chunk, waiting for buffer space if the channel is full
+    pub async fn send(&mut self, chunk: StreamChunk) -> Result<(), StreamClosed> {
+        self.push(Ok(chunk)).await
+    }
+
+    /// Send an error to the consumer, waiting for buffer space if needed
+    pub async fn send_error(&mut self, err: E) -> Result<(), StreamClosed> {
+        self.push(Err(err)).await
+    }
+
+    /// Whether the consumer has dropped its stream
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Wait for buffer space, then enqueue without waiting for delivery
+    async fn push(&mut self, item: Result<StreamChunk, E>) -> Result<(), StreamClosed> {
+        future::poll_fn(|cx| self.tx.poll_ready(cx))
+            .await
+            .map_err(|_| StreamClosed)?;
+        self.tx.start_send(item).map_err(|_| StreamClosed)
+    }
+}
+
+/// Consumer half of a [`token_channel`]
+pub struct ChannelTokenStream<E> {
+    rx: mpsc::Receiver<Result<StreamChunk, E>>,
+}
+
+impl<E> Stream for ChannelTokenStream<E> {
+    type Item = Result<StreamChunk, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_next_unpin(cx)
+    }
+}
+
+/// An incremental piece of synthesized speech
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    /// Encoded audio for this chunk
+    pub data: Vec<u8>,
+    pub format: AudioFormat,
+    /// Whether this is the final chunk
+    pub done: bool,
+}
+
+/// Speech model that can stream synthesized audio as it is generated.
+///
+/// Lets playback start before the whole utterance has been synthesized.
+/// Only text-to-speech input is expected to stream; providers may return
+/// a single chunk for other inputs.
+pub trait StreamingSpeechModel: SpeechModel {
+    /// Stream of audio chunks
+    type AudioStream: Stream<Item = Result<AudioChunk, Self::Error>> + Send;
+
+    /// Start synthesizing, yielding audio chunks as they arrive
+    fn stream_speech(&self, context: &Self::Context, input: AudioInput) -> Self::AudioStream;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioOutput, Model};
+
+    /// Mock TTS that "synthesizes" one chunk per word
+    struct WordTts;
+
+    impl Model for WordTts {
+        type Context = ();
+        type Input = AudioInput;
+        type Output = AudioOutput;
+        type Error = ();
+
+        async fn execute<'a>(
+            &'a self,
+            _context: &'a (),
+            input: AudioInput,
+        ) -> Result<AudioOutput, ()> {
+            let mut data = Vec::new();
+            let mut chunks = std::pin::pin!(self.stream_speech(&(), input));
+            while let Some(chunk) = chunks.next().await {
+                data.extend(chunk?.data);
+            }
+            Ok(AudioOutput::Audio {
+                data,
+                format: AudioFormat::Wav,
+                sample_rate: None,
+                channels: None,
+            })
+        }
+    }
+
+    impl SpeechModel for WordTts {}
+
+    impl StreamingSpeechModel for WordTts {
+        type AudioStream = futures::stream::Iter<std::vec::IntoIter<Result<AudioChunk, ()>>>;
+
+        fn stream_speech(&self, _context: &(), input: AudioInput) -> Self::AudioStream {
+            let AudioInput::TextToSpeech { text, .. } = input else {
+                return futures::stream::iter(vec![Err(())]);
+            };
+            let words: Vec<_> = text.split_whitespace().collect();
+            let chunks = words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    Ok(AudioChunk {
+                        data: word.as_bytes().to_vec(),
+                        format: AudioFormat::Wav,
+                        done: i + 1 == words.len(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            futures::stream::iter(chunks)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_collect_stream() {
+        let chunks: Vec<Result<StreamChunk, ()>> = vec![
+            Ok(StreamChunk::delta("Hello")),
+            Ok(StreamChunk::delta(", ")),
+            Ok(StreamChunk {
+                delta: "world".to_string(),
+                usage: Some(TokenUsage {
+                    prompt_tokens: 3,
+                    completion_tokens: 3,
+                    total_tokens: 6,
+                }),
+                done: true,
+                ..Default::default()
+            }),
+            // Anything after the final chunk is ignored
+            Ok(StreamChunk::delta("!")),
+        ];
+
+        let output = collect_stream(futures::stream::iter(chunks)).await.unwrap();
+        assert_eq!(output.text, "Hello, world");
+        assert_eq!(output.finish_reason, FinishReason::Stop);
+        assert_eq!(output.usage.total_tokens, 6);
+    }
+
+    #[tokio::test]
+    async fn test_token_channel() {
+        let (mut sink, stream) = token_channel::<()>(1);
+
+        let producer = async move {
+            for word in ["one ", "two ", "three"] {
+                sink.send(StreamChunk::delta(word)).await.unwrap();
+            }
+            sink.send(StreamChunk {
+                done: true,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        };
+        let (_, output) = futures::join!(producer, collect_stream(stream));
+
+        assert_eq!(output.unwrap().text, "one two three");
+    }
+
+    #[tokio::test]
+    async fn test_token_channel_backpressure() {
+        use futures::FutureExt;
+
+        let (mut sink, mut stream) = token_channel::<()>(0);
+
+        // A single sender gets one slot; the next send waits for the consumer
+        sink.send(StreamChunk::delta("a")).await.unwrap();
+        assert!(sink.send(StreamChunk::delta("b")).now_or_never().is_none());
+
+        assert_eq!(stream.next().await.unwrap().unwrap().delta, "a");
+        sink.send(StreamChunk::delta("b")).await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap().delta, "b");
+
+        drop(stream);
+        assert!(sink.is_closed());
+        assert_eq!(sink.send(StreamChunk::delta("c")).await, Err(StreamClosed));
+    }
+
+    #[tokio::test]
+    async fn test_collect_stream_propagates_error() {
+        let chunks = vec![Ok(StreamChunk::delta("partial")), Err("connection reset")];
+
+        let err = collect_stream(futures::stream::iter(chunks))
+            .await
+            .unwrap_err();
+        assert_eq!(err, "connection reset");
+    }
+
+    #[tokio::test]
+    async fn test_streaming_speech_model() {
+        let input = AudioInput::TextToSpeech {
+            text: "hello streaming world".to_string(),
+            voice: None,
+        };
+
+        let chunks: Vec<_> = WordTts.stream_speech(&(), input.clone()).collect().await;
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].as_ref().unwrap().data, b"hello");
+        assert!(!chunks[1].as_ref().unwrap().done);
+        assert!(chunks[2].as_ref().unwrap().done);
+
+        let AudioOutput::Audio { data, .. } = WordTts.execute(&(), input).await.unwrap() else {
+            panic!("expected audio output");
+        };
+        assert_eq!(data, b"hellostreamingworld");
+    }
+}
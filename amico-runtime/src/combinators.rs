@@ -0,0 +1,330 @@
+//! Workflow combinators
+
+use std::marker::PhantomData;
+
+use crate::Workflow;
+
+/// Extension methods for composing workflows
+pub trait WorkflowExt: Workflow + Sized {
+    /// Transform the output of a successful execution
+    fn map_output<F, O>(self, f: F) -> MapOutput<Self, F, O>
+    where
+        F: Fn(Self::Output) -> O,
+    {
+        MapOutput {
+            inner: self,
+            f,
+            _output: PhantomData,
+        }
+    }
+
+    /// Transform the error of a failed execution
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F, E>
+    where
+        F: Fn(Self::Error) -> E,
+    {
+        MapErr {
+            inner: self,
+            f,
+            _error: PhantomData,
+        }
+    }
+
+    /// Run `next` on the output of this workflow, sharing the same context
+    fn then<W2>(self, next: W2) -> Then<Self, W2>
+    where
+        W2: Workflow<Context = Self::Context, Input = Self::Output>,
+    {
+        Then { first: self, next }
+    }
+
+    /// Run this workflow and `other` concurrently on the same input
+    fn join<W2>(self, other: W2) -> Join<Self, W2>
+    where
+        W2: Workflow<Context = Self::Context, Input = Self::Input>,
+        Self::Input: Clone,
+    {
+        Join {
+            left: self,
+            right: other,
+        }
+    }
+}
+
+impl<W: Workflow> WorkflowExt for W {}
+
+/// Workflow returned by [`WorkflowExt::map_output`]
+pub struct MapOutput<W, F, O> {
+    inner: W,
+    f: F,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<W, F, O> Workflow for MapOutput<W, F, O>
+where
+    W: Workflow + Sync,
+    W::Context: Sync,
+    W::Input: Send,
+    F: Fn(W::Output) -> O + Sync,
+{
+    type Context = W::Context;
+    type Input = W::Input;
+    type Output = O;
+    type Error = W::Error;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner.execute(context, input).await.map(&self.f)
+    }
+}
+
+/// Workflow returned by [`WorkflowExt::map_err`]
+pub struct MapErr<W, F, E> {
+    inner: W,
+    f: F,
+    _error: PhantomData<fn() -> E>,
+}
+
+impl<W, F, E> Workflow for MapErr<W, F, E>
+where
+    W: Workflow + Sync,
+    W::Context: Sync,
+    W::Input: Send,
+    F: Fn(W::Error) -> E + Sync,
+{
+    type Context = W::Context;
+    type Input = W::Input;
+    type Output = W::Output;
+    type Error = E;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner.execute(context, input).await.map_err(&self.f)
+    }
+}
+
+/// Error of a [`Then`] workflow, reporting which stage failed
+#[derive(Debug, PartialEq, Eq)]
+pub enum ThenError<E1, E2> {
+    First(E1),
+    Second(E2),
+}
+
+impl<E1: std::fmt::Display, E2: std::fmt::Display> std::fmt::Display for ThenError<E1, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::First(err) => write!(f, "First workflow failed: {}", err),
+            Self::Second(err) => write!(f, "Second workflow failed: {}", err),
+        }
+    }
+}
+
+impl<E1, E2> std::error::Error for ThenError<E1, E2>
+where
+    E1: std::fmt::Debug + std::fmt::Display,
+    E2: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+/// Workflow returned by [`WorkflowExt::then`]
+pub struct Then<W1, W2> {
+    first: W1,
+    next: W2,
+}
+
+impl<W1, W2> Workflow for Then<W1, W2>
+where
+    W1: Workflow + Sync,
+    W1::Context: Sync,
+    W1::Input: Send,
+    W1::Output: Send,
+    W1::Error: Send,
+    W2: Workflow<Context = W1::Context, Input = W1::Output> + Sync,
+{
+    type Context = W1::Context;
+    type Input = W1::Input;
+    type Output = W2::Output;
+    type Error = ThenError<W1::Error, W2::Error>;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        let intermediate = self
+            .first
+            .execute(context, input)
+            .await
+            .map_err(ThenError::First)?;
+        self.next
+            .execute(context, intermediate)
+            .await
+            .map_err(ThenError::Second)
+    }
+}
+
+/// Error of a [`Join`] workflow, reporting which side failed
+#[derive(Debug, PartialEq, Eq)]
+pub enum JoinError<E1, E2> {
+    Left(E1),
+    Right(E2),
+    Both(E1, E2),
+}
+
+impl<E1: std::fmt::Display, E2: std::fmt::Display> std::fmt::Display for JoinError<E1, E2> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left(err) => write!(f, "Left workflow failed: {}", err),
+            Self::Right(err) => write!(f, "Right workflow failed: {}", err),
+            Self::Both(left, right) => {
+                write!(f, "Both workflows failed: {}; {}", left, right)
+            }
+        }
+    }
+}
+
+impl<E1, E2> std::error::Error for JoinError<E1, E2>
+where
+    E1: std::fmt::Debug + std::fmt::Display,
+    E2: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+/// Workflow returned by [`WorkflowExt::join`]
+pub struct Join<W1, W2> {
+    left: W1,
+    right: W2,
+}
+
+impl<W1, W2> Workflow for Join<W1, W2>
+where
+    W1: Workflow + Sync,
+    W1::Context: Sync,
+    W1::Input: Clone + Send,
+    W1::Output: Send,
+    W1::Error: Send,
+    W2: Workflow<Context = W1::Context, Input = W1::Input> + Sync,
+    W2::Output: Send,
+    W2::Error: Send,
+{
+    type Context = W1::Context;
+    type Input = W1::Input;
+    type Output = (W1::Output, W2::Output);
+    type Error = JoinError<W1::Error, W2::Error>;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        let (left, right) = futures::join!(
+            self.left.execute(context, input.clone()),
+            self.right.execute(context, input)
+        );
+        match (left, right) {
+            (Ok(left), Ok(right)) => Ok((left, right)),
+            (Err(left), Ok(_)) => Err(JoinError::Left(left)),
+            (Ok(_), Err(right)) => Err(JoinError::Right(right)),
+            (Err(left), Err(right)) => Err(JoinError::Both(left, right)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses its input as an integer
+    struct Parse;
+
+    impl Workflow for Parse {
+        type Context = ();
+        type Input = String;
+        type Output = i64;
+        type Error = std::num::ParseIntError;
+
+        async fn execute<'a>(
+            &'a self,
+            _context: &'a (),
+            input: String,
+        ) -> Result<i64, Self::Error> {
+            input.parse()
+        }
+    }
+
+    /// Looks a number up in a fixed table
+    struct Lookup;
+
+    impl Workflow for Lookup {
+        type Context = ();
+        type Input = i64;
+        type Output = &'static str;
+        type Error = String;
+
+        async fn execute<'a>(
+            &'a self,
+            _context: &'a (),
+            input: i64,
+        ) -> Result<Self::Output, String> {
+            match input {
+                1 => Ok("one"),
+                2 => Ok("two"),
+                n => Err(format!("no entry for {}", n)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_map_output() {
+        let workflow = Parse.map_output(|n| n * 2);
+        assert_eq!(workflow.execute(&(), "21".to_string()).await.unwrap(), 42);
+        assert!(workflow.execute(&(), "x".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_map_err() {
+        let workflow = Parse.map_err(|err| format!("bad input: {}", err));
+        assert_eq!(workflow.execute(&(), "7".to_string()).await, Ok(7));
+        assert_eq!(
+            workflow.execute(&(), "x".to_string()).await,
+            Err("bad input: invalid digit found in string".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_then() {
+        let pipeline = Parse.then(Lookup);
+        assert_eq!(pipeline.execute(&(), "2".to_string()).await, Ok("two"));
+        assert!(matches!(
+            pipeline.execute(&(), "x".to_string()).await,
+            Err(ThenError::First(_))
+        ));
+        assert_eq!(
+            pipeline.execute(&(), "9".to_string()).await,
+            Err(ThenError::Second("no entry for 9".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_join() {
+        let both = Parse.join(Parse.map_output(|n| n + 1));
+        assert_eq!(both.execute(&(), "1".to_string()).await, Ok((1, 2)));
+
+        let lookup = Parse.join(Parse.then(Lookup));
+        assert_eq!(lookup.execute(&(), "1".to_string()).await, Ok((1, "one")));
+        assert!(matches!(
+            lookup.execute(&(), "5".to_string()).await,
+            Err(JoinError::Right(ThenError::Second(_)))
+        ));
+        assert!(matches!(
+            lookup.execute(&(), "x".to_string()).await,
+            Err(JoinError::Both(_, ThenError::First(_)))
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+//! Cancellation and deadlines for workflow execution
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Cooperative cancellation flag shared between a caller and a workflow.
+///
+/// Clones share the same flag, so the caller keeps one clone and hands
+/// another to the context. Workflows check it between steps; nothing is
+/// interrupted mid-call.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every workflow holding this token
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether cancellation has been requested
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Execution context that may carry a cancellation token and a deadline.
+///
+/// Both are optional and absent by default, so any context can opt in with
+/// an empty `impl ControlContext for MyContext {}`.
+pub trait ControlContext {
+    /// Cancellation token for this execution, if any
+    fn cancellation(&self) -> Option<&CancellationToken> {
+        None
+    }
+
+    /// Absolute time by which execution should finish, if any
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
+
+    /// Whether cancellation has been requested
+    fn is_cancelled(&self) -> bool {
+        self.cancellation()
+            .is_some_and(|token| token.is_cancelled())
+    }
+
+    /// Time left until the deadline, or `None` without a deadline
+    fn remaining(&self) -> Option<Duration> {
+        self.deadline()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether the deadline has passed
+    fn is_expired(&self) -> bool {
+        self.deadline()
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Whether a workflow should stop: cancelled or past its deadline
+    fn should_stop(&self) -> bool {
+        self.is_cancelled() || self.is_expired()
+    }
+}
+
+impl ControlContext for () {}
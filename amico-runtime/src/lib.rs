@@ -30,6 +30,22 @@
 //! ```
 
 use std::future::Future;
+use std::time::Instant;
+
+mod combinators;
+pub use combinators::{Join, JoinError, MapErr, MapOutput, Then, ThenError, WorkflowExt};
+
+mod control;
+pub use control::{CancellationToken, ControlContext};
+
+mod metrics;
+pub use metrics::{HistogramSummary, InMemoryMetrics, Labels, Metrics, MetricsSnapshot};
+
+mod time;
+pub use time::Monotonic;
+
+mod trace;
+pub use trace::{SpanContext, SpanRecord, TracedContext};
 
 /// Workflow trait - defines a unit of work that can be executed
 pub trait Workflow {
@@ -189,11 +205,38 @@ pub trait ShortLivedRuntime: Runtime {
 pub struct SimpleContext<S, P> {
     state: S,
     permissions: P,
+    span: Option<SpanContext>,
+    cancellation: Option<CancellationToken>,
+    deadline: Option<Instant>,
 }
 
 impl<S, P> SimpleContext<S, P> {
     pub fn new(state: S, permissions: P) -> Self {
-        Self { state, permissions }
+        Self {
+            state,
+            permissions,
+            span: None,
+            cancellation: None,
+            deadline: None,
+        }
+    }
+
+    /// Stop execution once `deadline` has passed
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Stop execution once `token` is cancelled
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// Attach a tracing span to the context
+    pub fn with_span(mut self, span: SpanContext) -> Self {
+        self.span = Some(span);
+        self
     }
 }
 
@@ -213,3 +256,52 @@ impl<S, P> ExecutionContext for SimpleContext<S, P> {
         &self.permissions
     }
 }
+
+impl<S, P> TracedContext for SimpleContext<S, P> {
+    fn span(&self) -> Option<&SpanContext> {
+        self.span.as_ref()
+    }
+}
+
+impl<S, P> ControlContext for SimpleContext<S, P> {
+    fn cancellation(&self) -> Option<&CancellationToken> {
+        self.cancellation.as_ref()
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_simple_context_control() {
+        let context = SimpleContext::new(0u32, ());
+        assert!(context.cancellation().is_none());
+        assert!(context.remaining().is_none());
+        assert!(!context.should_stop());
+
+        let token = CancellationToken::new();
+        let context = SimpleContext::new(0u32, ())
+            .with_cancellation(token.clone())
+            .with_deadline(Instant::now() + Duration::from_secs(60));
+        assert!(!context.should_stop());
+        assert!(context.remaining().unwrap() > Duration::from_secs(30));
+
+        token.cancel();
+        assert!(context.is_cancelled());
+        assert!(context.should_stop());
+    }
+
+    #[test]
+    fn test_simple_context_deadline_expires() {
+        let context = SimpleContext::new((), ()).with_deadline(Instant::now());
+        assert!(context.is_expired());
+        assert_eq!(context.remaining(), Some(Duration::ZERO));
+        assert!(context.should_stop());
+    }
+}
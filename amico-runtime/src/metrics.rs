@@ -0,0 +1,260 @@
+//! Metrics collection for workflows, models and tools
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::SpanRecord;
+
+/// Label set attached to a metric, e.g. `[("model", "gpt-4o")]`
+pub type Labels<'a> = &'a [(&'a str, &'a str)];
+
+/// Sink for counters and histograms.
+///
+/// Implementations decide how metrics are stored or exported; bridge this
+/// trait to Prometheus, StatsD or OpenTelemetry to ship them elsewhere.
+/// [`InMemoryMetrics`] keeps everything in memory for tests and simple
+/// deployments.
+pub trait Metrics {
+    /// Add `value` to a monotonically increasing counter
+    fn increment_counter(&self, name: &str, labels: Labels<'_>, value: u64);
+
+    /// Record a single observation in a histogram
+    fn record_histogram(&self, name: &str, labels: Labels<'_>, value: f64);
+
+    /// Record a completed tracing span. Ignored unless overridden.
+    fn record_span(&self, _span: &SpanRecord) {}
+}
+
+impl<T: Metrics + ?Sized> Metrics for &T {
+    fn increment_counter(&self, name: &str, labels: Labels<'_>, value: u64) {
+        (**self).increment_counter(name, labels, value)
+    }
+
+    fn record_histogram(&self, name: &str, labels: Labels<'_>, value: f64) {
+        (**self).record_histogram(name, labels, value)
+    }
+
+    fn record_span(&self, span: &SpanRecord) {
+        (**self).record_span(span)
+    }
+}
+
+impl<T: Metrics + ?Sized> Metrics for Arc<T> {
+    fn increment_counter(&self, name: &str, labels: Labels<'_>, value: u64) {
+        (**self).increment_counter(name, labels, value)
+    }
+
+    fn record_histogram(&self, name: &str, labels: Labels<'_>, value: f64) {
+        (**self).record_histogram(name, labels, value)
+    }
+
+    fn record_span(&self, span: &SpanRecord) {
+        (**self).record_span(span)
+    }
+}
+
+/// Summary of the observations recorded in a histogram
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramSummary {
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl HistogramSummary {
+    /// Mean of all observations
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+
+    fn record(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+}
+
+impl Default for HistogramSummary {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+/// Point-in-time copy of the metrics held by [`InMemoryMetrics`].
+///
+/// Series are keyed Prometheus-style as `name{label="value",...}` with
+/// labels sorted by name.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    pub counters: BTreeMap<String, u64>,
+    pub histograms: BTreeMap<String, HistogramSummary>,
+    /// The most recently completed spans, oldest first
+    pub spans: Vec<SpanRecord>,
+}
+
+impl MetricsSnapshot {
+    /// Value of a counter, or 0 if it was never incremented
+    pub fn counter(&self, name: &str, labels: Labels<'_>) -> u64 {
+        self.counters
+            .get(&series_key(name, labels))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Summary of a histogram, if it has any observations
+    pub fn histogram(&self, name: &str, labels: Labels<'_>) -> Option<HistogramSummary> {
+        self.histograms.get(&series_key(name, labels)).copied()
+    }
+}
+
+fn series_key(name: &str, labels: Labels<'_>) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+    let mut labels = labels.to_vec();
+    labels.sort_unstable();
+    let labels = labels
+        .iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, value))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{}{{{}}}", name, labels)
+}
+
+/// Number of spans [`InMemoryMetrics`] keeps by default
+const DEFAULT_SPAN_CAPACITY: usize = 1024;
+
+/// Thread-safe in-memory `Metrics` implementation.
+///
+/// Counters and histograms are aggregated, so they take constant memory
+/// per series. Spans are kept in a ring buffer: once `span_capacity` spans
+/// are stored, each new span evicts the oldest one.
+#[derive(Debug)]
+pub struct InMemoryMetrics {
+    inner: Mutex<MetricsSnapshot>,
+    spans: Mutex<VecDeque<SpanRecord>>,
+    span_capacity: usize,
+}
+
+impl InMemoryMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep at most the `capacity` most recent spans (1024 by default)
+    pub fn with_span_capacity(mut self, capacity: usize) -> Self {
+        self.span_capacity = capacity;
+        self
+    }
+
+    /// Copy the current values of all metrics
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let mut snapshot = self.inner.lock().unwrap().clone();
+        snapshot.spans = self.spans.lock().unwrap().iter().cloned().collect();
+        snapshot
+    }
+}
+
+impl Default for InMemoryMetrics {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(MetricsSnapshot::default()),
+            spans: Mutex::new(VecDeque::new()),
+            span_capacity: DEFAULT_SPAN_CAPACITY,
+        }
+    }
+}
+
+impl Metrics for InMemoryMetrics {
+    fn increment_counter(&self, name: &str, labels: Labels<'_>, value: u64) {
+        *self
+            .inner
+            .lock()
+            .unwrap()
+            .counters
+            .entry(series_key(name, labels))
+            .or_default() += value;
+    }
+
+    fn record_histogram(&self, name: &str, labels: Labels<'_>, value: f64) {
+        self.inner
+            .lock()
+            .unwrap()
+            .histograms
+            .entry(series_key(name, labels))
+            .or_default()
+            .record(value);
+    }
+
+    fn record_span(&self, span: &SpanRecord) {
+        if self.span_capacity == 0 {
+            return;
+        }
+        let mut spans = self.spans.lock().unwrap();
+        if spans.len() >= self.span_capacity {
+            spans.pop_front();
+        }
+        spans.push_back(span.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpanContext;
+    use std::time::Duration;
+
+    #[test]
+    fn test_in_memory_metrics() {
+        let metrics = InMemoryMetrics::new();
+        metrics.increment_counter("requests_total", &[("model", "a"), ("status", "ok")], 1);
+        metrics.increment_counter("requests_total", &[("status", "ok"), ("model", "a")], 2);
+        metrics.record_histogram("latency_ms", &[], 10.0);
+        metrics.record_histogram("latency_ms", &[], 30.0);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(
+            snapshot.counter("requests_total", &[("model", "a"), ("status", "ok")]),
+            3
+        );
+        assert_eq!(snapshot.counter("requests_total", &[("model", "b")]), 0);
+
+        let latency = snapshot.histogram("latency_ms", &[]).unwrap();
+        assert_eq!(latency.count, 2);
+        assert_eq!(latency.min, 10.0);
+        assert_eq!(latency.max, 30.0);
+        assert_eq!(latency.mean(), 20.0);
+    }
+
+    #[test]
+    fn test_in_memory_metrics_caps_spans() {
+        let metrics = InMemoryMetrics::new().with_span_capacity(2);
+        for name in ["first", "second", "third"] {
+            metrics.record_span(&SpanRecord {
+                name: name.to_string(),
+                context: SpanContext::new_root(),
+                duration: Duration::ZERO,
+                ok: true,
+            });
+        }
+
+        let names: Vec<_> = metrics
+            .snapshot()
+            .spans
+            .into_iter()
+            .map(|span| span.name)
+            .collect();
+        assert_eq!(names, ["second", "third"]);
+    }
+}
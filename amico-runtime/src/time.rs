@@ -0,0 +1,63 @@
+//! Monotonic clock that works in browser builds
+//!
+//! `std::time::Instant` panics on `wasm32-unknown-unknown`, so browser
+//! builds read the clock through `js_sys::Date` instead.
+
+use std::time::Duration;
+
+/// Monotonic clock for measuring durations.
+///
+/// Unlike a wall clock, elapsed time never goes backwards when the system
+/// time is adjusted. On wasm the browser offers no monotonic clock without
+/// `web-sys`, so the wall clock is used and clamped at zero.
+#[derive(Debug, Clone, Copy)]
+pub struct Monotonic {
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    start: std::time::Instant,
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    start: f64,
+}
+
+impl Monotonic {
+    /// Start measuring from now
+    pub fn start() -> Self {
+        Self {
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            start: std::time::Instant::now(),
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            start: js_sys::Date::now(),
+        }
+    }
+
+    /// Time elapsed since `start`
+    pub fn elapsed(&self) -> Duration {
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            self.start.elapsed()
+        }
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            Duration::from_secs_f64((js_sys::Date::now() - self.start).max(0.0) / 1000.0)
+        }
+    }
+
+    /// Time elapsed since `start`, in whole milliseconds
+    pub fn elapsed_millis(&self) -> u64 {
+        self.elapsed().as_millis() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_monotonic_elapsed() {
+        let clock = Monotonic::start();
+        std::thread::sleep(Duration::from_millis(5));
+        let first = clock.elapsed();
+        assert!(first >= Duration::from_millis(5));
+        assert!(clock.elapsed() >= first);
+        assert!(clock.elapsed_millis() >= 5);
+    }
+}
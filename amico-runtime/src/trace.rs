@@ -0,0 +1,104 @@
+//! Trace and span identifiers for distributed tracing
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Generate a random, non-zero 64-bit id without an RNG dependency
+fn random_id() -> u64 {
+    static STATE: OnceLock<RandomState> = OnceLock::new();
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let mut hasher = STATE.get_or_init(RandomState::new).build_hasher();
+    hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+    hasher.finish().max(1)
+}
+
+/// Identifies a span within a trace, following the W3C Trace Context model
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SpanContext {
+    /// Id shared by every span in the trace
+    pub trace_id: u128,
+    /// Id of this span
+    pub span_id: u64,
+    /// Id of the span that started this one, if any
+    pub parent_span_id: Option<u64>,
+}
+
+impl SpanContext {
+    /// Start a new trace
+    pub fn new_root() -> Self {
+        Self {
+            trace_id: ((random_id() as u128) << 64) | random_id() as u128,
+            span_id: random_id(),
+            parent_span_id: None,
+        }
+    }
+
+    /// Create a span in the same trace whose parent is this span
+    pub fn child(&self) -> Self {
+        Self {
+            trace_id: self.trace_id,
+            span_id: random_id(),
+            parent_span_id: Some(self.span_id),
+        }
+    }
+
+    /// Encode as a W3C `traceparent` header value
+    pub fn traceparent(&self) -> String {
+        format!("00-{:032x}-{:016x}-01", self.trace_id, self.span_id)
+    }
+}
+
+/// Execution context that may carry a tracing span.
+///
+/// Tracing is optional: the default implementation carries no span, so
+/// any context can opt in with an empty `impl TracedContext for MyContext {}`.
+pub trait TracedContext {
+    /// The current span, if tracing is enabled
+    fn span(&self) -> Option<&SpanContext> {
+        None
+    }
+
+    /// Trace id of the current span
+    fn trace_id(&self) -> Option<u128> {
+        self.span().map(|span| span.trace_id)
+    }
+
+    /// Id of the current span
+    fn span_id(&self) -> Option<u64> {
+        self.span().map(|span| span.span_id)
+    }
+}
+
+impl TracedContext for () {}
+
+/// A completed span, as reported to `Metrics::record_span`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanRecord {
+    /// Operation name, e.g. `model gpt-4o` or `tool search`
+    pub name: String,
+    pub context: SpanContext,
+    pub duration: Duration,
+    /// Whether the operation succeeded
+    pub ok: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_child_span_records_parent() {
+        let root = SpanContext::new_root();
+        let child = root.child();
+
+        assert_eq!(root.parent_span_id, None);
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_eq!(child.parent_span_id, Some(root.span_id));
+        assert_ne!(child.span_id, root.span_id);
+        assert_eq!(child.traceparent().len(), 55);
+    }
+}
@@ -0,0 +1,248 @@
+//! Deny-all system for sandboxed agents
+
+use std::marker::PhantomData;
+
+use crate::{
+    FileOperation, FileResult, NetworkOperation, NetworkResult, PermissionDenied, ProcessOperation,
+    ProcessResult, System, SystemCapabilities, SystemOperation, Tool,
+};
+
+/// Tool that rejects every operation with `PermissionDenied`
+pub struct Denied<I, O> {
+    _types: PhantomData<fn(I) -> O>,
+}
+
+impl<I, O> Denied<I, O> {
+    pub const fn new() -> Self {
+        Self {
+            _types: PhantomData,
+        }
+    }
+}
+
+impl<I, O> Default for Denied<I, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, O> Tool for Denied<I, O>
+where
+    I: SystemOperation + Send,
+    O: Send,
+{
+    type Input = I;
+    type Output = O;
+    type Error = PermissionDenied;
+
+    async fn execute(&self, input: I) -> Result<O, PermissionDenied> {
+        Err(PermissionDenied(input.required_permission()))
+    }
+
+    fn name(&self) -> &str {
+        I::KIND
+    }
+
+    fn description(&self) -> &str {
+        "Denies every operation"
+    }
+}
+
+pub type DeniedFileOps = Denied<FileOperation, FileResult>;
+pub type DeniedNetworkOps = Denied<NetworkOperation, NetworkResult>;
+pub type DeniedProcessOps = Denied<ProcessOperation, ProcessResult>;
+
+/// `System` whose operations all fail with `PermissionDenied`.
+///
+/// A safe baseline for running untrusted agent logic. The `with_*`
+/// builders swap in real tools for the operations that should be allowed,
+/// and `capabilities` reports exactly those as supported.
+///
+/// ```rust,ignore
+/// let system = DeniedSystem::new().with_file_ops(JailedFileOps::new("workspace")?);
+/// ```
+pub struct DeniedSystem<F = DeniedFileOps, N = DeniedNetworkOps, P = DeniedProcessOps> {
+    file_ops: F,
+    network_ops: N,
+    process_ops: P,
+    capabilities: SystemCapabilities,
+}
+
+impl DeniedSystem {
+    /// A system that denies every operation
+    pub fn new() -> Self {
+        Self {
+            file_ops: Denied::new(),
+            network_ops: Denied::new(),
+            process_ops: Denied::new(),
+            capabilities: SystemCapabilities::none(),
+        }
+    }
+}
+
+impl Default for DeniedSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F, N, P> DeniedSystem<F, N, P> {
+    /// Allow file operations through `file_ops`
+    pub fn with_file_ops<T: Tool>(self, file_ops: T) -> DeniedSystem<T, N, P> {
+        DeniedSystem {
+            file_ops,
+            network_ops: self.network_ops,
+            process_ops: self.process_ops,
+            capabilities: SystemCapabilities {
+                file: true,
+                ..self.capabilities
+            },
+        }
+    }
+
+    /// Allow network operations through `network_ops`
+    pub fn with_network_ops<T: Tool>(self, network_ops: T) -> DeniedSystem<F, T, P> {
+        DeniedSystem {
+            file_ops: self.file_ops,
+            network_ops,
+            process_ops: self.process_ops,
+            capabilities: SystemCapabilities {
+                network: true,
+                ..self.capabilities
+            },
+        }
+    }
+
+    /// Allow process operations through `process_ops`
+    pub fn with_process_ops<T: Tool>(self, process_ops: T) -> DeniedSystem<F, N, T> {
+        DeniedSystem {
+            file_ops: self.file_ops,
+            network_ops: self.network_ops,
+            process_ops,
+            capabilities: SystemCapabilities {
+                process: true,
+                ..self.capabilities
+            },
+        }
+    }
+}
+
+impl<F: Tool, N: Tool, P: Tool> System for DeniedSystem<F, N, P> {
+    type FileOps = F;
+    type NetworkOps = N;
+    type ProcessOps = P;
+
+    fn file_ops(&self) -> &F {
+        &self.file_ops
+    }
+
+    fn network_ops(&self) -> &N {
+        &self.network_ops
+    }
+
+    fn process_ops(&self) -> &P {
+        &self.process_ops
+    }
+
+    fn capabilities(&self) -> SystemCapabilities {
+        self.capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResourcePermission;
+
+    #[tokio::test]
+    async fn test_denied_system_file_ops() {
+        let system = DeniedSystem::new();
+        let err = system
+            .file_ops()
+            .execute(FileOperation::Write {
+                path: "out.txt".to_string(),
+                content: vec![],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            PermissionDenied(ResourcePermission::FileWrite("out.txt".to_string()))
+        );
+        assert_eq!(system.file_ops().name(), "file_ops");
+        assert_eq!(system.capabilities(), SystemCapabilities::none());
+    }
+
+    #[tokio::test]
+    async fn test_denied_system_network_ops() {
+        let err = DeniedSystem::new()
+            .network_ops()
+            .execute(NetworkOperation::HttpRequest {
+                method: "GET".to_string(),
+                url: "https://user@Example.com:8443/path?q=1".to_string(),
+                headers: vec![],
+                body: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Permission denied: NetworkAccess(\"example.com\")"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_denied_system_process_ops() {
+        let err = DeniedSystem::new()
+            .process_ops()
+            .execute(ProcessOperation::Execute {
+                command: "ls".to_string(),
+                args: vec![],
+                env: vec![],
+                timeout: None,
+                max_output_bytes: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err, PermissionDenied(ResourcePermission::ProcessExecution));
+    }
+
+    #[tokio::test]
+    async fn test_denied_system_override() {
+        struct Touch;
+
+        impl Tool for Touch {
+            type Input = FileOperation;
+            type Output = FileResult;
+            type Error = PermissionDenied;
+
+            async fn execute(&self, _input: FileOperation) -> Result<FileResult, PermissionDenied> {
+                Ok(FileResult::Success)
+            }
+
+            fn name(&self) -> &str {
+                "file_ops"
+            }
+
+            fn description(&self) -> &str {
+                "Pretends to touch files"
+            }
+        }
+
+        let system = DeniedSystem::new().with_file_ops(Touch);
+        let result = system
+            .file_ops()
+            .execute(FileOperation::Delete {
+                path: "tmp.txt".to_string(),
+            })
+            .await;
+        assert!(matches!(result, Ok(FileResult::Success)));
+        assert_eq!(
+            system.capabilities(),
+            SystemCapabilities {
+                file: true,
+                ..SystemCapabilities::none()
+            }
+        );
+    }
+}
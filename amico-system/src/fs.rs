@@ -0,0 +1,367 @@
+//! Local file system tools (requires the `fs` feature)
+
+use std::io::{ErrorKind, SeekFrom};
+use std::path::{Component, Path, PathBuf};
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::{FileOperation, FileResult, Tool};
+
+/// File tool error types
+#[derive(Debug)]
+pub enum FileToolError {
+    /// The path resolves outside the jail root
+    OutsideJail(String),
+    /// The file system operation failed
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for FileToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OutsideJail(path) => write!(f, "Path is outside the allowed root: {}", path),
+            Self::Io(err) => write!(f, "File operation failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for FileToolError {}
+
+impl From<std::io::Error> for FileToolError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Read at most `max_bytes` from `offset`, reporting whether more follows.
+///
+/// Only `max_bytes + 1` bytes are ever buffered, so huge files cannot
+/// exhaust memory.
+async fn read_bounded(path: &Path, offset: u64, max_bytes: usize) -> std::io::Result<FileResult> {
+    let mut file = tokio::fs::File::open(path).await?;
+    if offset > 0 {
+        file.seek(SeekFrom::Start(offset)).await?;
+    }
+
+    let mut data = Vec::new();
+    file.take((max_bytes as u64).saturating_add(1))
+        .read_to_end(&mut data)
+        .await?;
+    let truncated = data.len() > max_bytes;
+    data.truncate(max_bytes);
+    Ok(FileResult::Content { data, truncated })
+}
+
+/// Run `operation` against `path`, ignoring the path inside the operation
+async fn run(operation: FileOperation, path: &Path) -> Result<FileResult, FileToolError> {
+    match operation {
+        FileOperation::Read {
+            max_bytes: None, ..
+        } => Ok(FileResult::Content {
+            data: tokio::fs::read(path).await?,
+            truncated: false,
+        }),
+        FileOperation::Read {
+            max_bytes: Some(max_bytes),
+            ..
+        } => Ok(read_bounded(path, 0, max_bytes).await?),
+        FileOperation::ReadChunk {
+            offset, max_bytes, ..
+        } => Ok(read_bounded(path, offset, max_bytes).await?),
+        FileOperation::Write { content, .. } => {
+            tokio::fs::write(path, content).await?;
+            Ok(FileResult::Success)
+        }
+        FileOperation::Delete { .. } => {
+            tokio::fs::remove_file(path).await?;
+            Ok(FileResult::Success)
+        }
+        FileOperation::List { .. } => {
+            let mut entries = tokio::fs::read_dir(path).await?;
+            let mut names = Vec::new();
+            while let Some(entry) = entries.next_entry().await? {
+                names.push(entry.file_name().to_string_lossy().into_owned());
+            }
+            names.sort();
+            Ok(FileResult::Listing(names))
+        }
+    }
+}
+
+/// File tool with unrestricted access to the local file system
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalFileOps;
+
+impl Tool for LocalFileOps {
+    type Input = FileOperation;
+    type Output = FileResult;
+    type Error = FileToolError;
+
+    async fn execute(&self, operation: FileOperation) -> Result<FileResult, FileToolError> {
+        let path = PathBuf::from(operation.path());
+        run(operation, &path).await
+    }
+
+    fn name(&self) -> &str {
+        "file_ops"
+    }
+
+    fn description(&self) -> &str {
+        "Read, write, delete and list local files"
+    }
+}
+
+/// File tool confined to a root directory.
+///
+/// Relative paths are resolved against the root. Every path is
+/// canonicalized, following `..` and symlinks, and rejected with
+/// `FileToolError::OutsideJail` unless it resolves inside the root. Files
+/// that do not exist yet (for writes) are checked through their parent
+/// directory; dangling symlinks are always rejected.
+///
+/// Paths are checked before the operation runs, so a symlink swapped in
+/// between the check and the operation is not caught; don't share the
+/// root with untrusted writers.
+#[derive(Debug, Clone)]
+pub struct JailedFileOps {
+    root: PathBuf,
+}
+
+impl JailedFileOps {
+    /// Confine file operations to `root`, which must exist
+    pub fn new(root: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            root: root.as_ref().canonicalize()?,
+        })
+    }
+
+    /// The canonical jail root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve `path` to a canonical path inside the root
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, FileToolError> {
+        let outside = || FileToolError::OutsideJail(path.to_string());
+        let joined = self.root.join(path);
+
+        let resolved = match joined.canonicalize() {
+            Ok(resolved) => resolved,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                // A dangling symlink could point anywhere, including at a
+                // file outside the root that a write would then create
+                if joined
+                    .symlink_metadata()
+                    .is_ok_and(|meta| meta.file_type().is_symlink())
+                {
+                    return Err(outside());
+                }
+                // The file itself may not exist yet; its parent must
+                let name = match joined.components().next_back() {
+                    Some(Component::Normal(name)) => name.to_owned(),
+                    _ => return Err(outside()),
+                };
+                let parent = joined.parent().ok_or_else(outside)?;
+                parent.canonicalize()?.join(name)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if resolved.starts_with(&self.root) {
+            Ok(resolved)
+        } else {
+            Err(outside())
+        }
+    }
+}
+
+impl Tool for JailedFileOps {
+    type Input = FileOperation;
+    type Output = FileResult;
+    type Error = FileToolError;
+
+    async fn execute(&self, operation: FileOperation) -> Result<FileResult, FileToolError> {
+        let path = self.resolve(operation.path())?;
+        run(operation, &path).await
+    }
+
+    fn name(&self) -> &str {
+        "file_ops"
+    }
+
+    fn description(&self) -> &str {
+        "Read, write, delete and list files inside a directory"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("amico-fs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read(path: &str) -> FileOperation {
+        FileOperation::Read {
+            path: path.to_string(),
+            max_bytes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jailed_read_inside_root() {
+        let dir = temp_dir("inside");
+        std::fs::create_dir(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("docs/notes.txt"), b"hello").unwrap();
+        let jail = JailedFileOps::new(&dir).unwrap();
+
+        let result = jail.execute(read("docs/notes.txt")).await.unwrap();
+        assert!(matches!(result, FileResult::Content { data, .. } if data == b"hello"));
+
+        // `..` that stays inside the root is fine
+        let result = jail.execute(read("docs/../docs/notes.txt")).await;
+        assert!(result.is_ok());
+
+        jail.execute(FileOperation::Write {
+            path: "new.txt".to_string(),
+            content: b"new".to_vec(),
+        })
+        .await
+        .unwrap();
+        assert_eq!(std::fs::read(dir.join("new.txt")).unwrap(), b"new");
+    }
+
+    #[tokio::test]
+    async fn test_jailed_rejects_parent_escape() {
+        let dir = temp_dir("parent");
+        std::fs::create_dir(dir.join("jail")).unwrap();
+        std::fs::write(dir.join("secret.txt"), b"secret").unwrap();
+        let jail = JailedFileOps::new(dir.join("jail")).unwrap();
+
+        let err = jail.execute(read("../secret.txt")).await.unwrap_err();
+        assert!(matches!(err, FileToolError::OutsideJail(_)));
+
+        let absolute = dir.join("secret.txt");
+        let err = jail
+            .execute(read(absolute.to_str().unwrap()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FileToolError::OutsideJail(_)));
+
+        let err = jail
+            .execute(FileOperation::Write {
+                path: "../escaped.txt".to_string(),
+                content: vec![],
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FileToolError::OutsideJail(_)));
+        assert!(!dir.join("escaped.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_jailed_rejects_symlink_escape() {
+        let dir = temp_dir("symlink");
+        std::fs::create_dir(dir.join("jail")).unwrap();
+        std::fs::write(dir.join("secret.txt"), b"secret").unwrap();
+        std::os::unix::fs::symlink(dir.join("secret.txt"), dir.join("jail/link.txt")).unwrap();
+        let jail = JailedFileOps::new(dir.join("jail")).unwrap();
+
+        let err = jail.execute(read("link.txt")).await.unwrap_err();
+        assert!(matches!(err, FileToolError::OutsideJail(_)));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_jailed_rejects_dangling_symlink_write() {
+        let dir = temp_dir("dangling");
+        std::fs::create_dir(dir.join("jail")).unwrap();
+        std::os::unix::fs::symlink("../outside.txt", dir.join("jail/link")).unwrap();
+        let jail = JailedFileOps::new(dir.join("jail")).unwrap();
+
+        let err = jail
+            .execute(FileOperation::Write {
+                path: "link".to_string(),
+                content: b"escaped".to_vec(),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, FileToolError::OutsideJail(_)));
+        assert!(!dir.join("outside.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_bounded_read() {
+        let dir = temp_dir("bounded");
+        std::fs::write(dir.join("small.txt"), b"hello").unwrap();
+        std::fs::write(dir.join("large.bin"), vec![7u8; 4096]).unwrap();
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+
+        let result = LocalFileOps
+            .execute(FileOperation::Read {
+                path: path("small.txt"),
+                max_bytes: Some(5),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            FileResult::Content { data, truncated: false } if data == b"hello"
+        ));
+
+        let result = LocalFileOps
+            .execute(FileOperation::Read {
+                path: path("large.bin"),
+                max_bytes: Some(1000),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            FileResult::Content { data, truncated: true } if data.len() == 1000
+        ));
+
+        // A limit of usize::MAX must not overflow
+        let result = LocalFileOps
+            .execute(FileOperation::Read {
+                path: path("small.txt"),
+                max_bytes: Some(usize::MAX),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            result,
+            FileResult::Content { data, truncated: false } if data == b"hello"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_chunked_read() {
+        let dir = temp_dir("chunked");
+        std::fs::write(dir.join("data.txt"), b"abcdefghij").unwrap();
+        let jail = JailedFileOps::new(&dir).unwrap();
+        let chunk = |offset| FileOperation::ReadChunk {
+            path: "data.txt".to_string(),
+            offset,
+            max_bytes: 4,
+        };
+
+        let result = jail.execute(chunk(4)).await.unwrap();
+        assert!(matches!(
+            result,
+            FileResult::Content { data, truncated: true } if data == b"efgh"
+        ));
+
+        let result = jail.execute(chunk(8)).await.unwrap();
+        assert!(matches!(
+            result,
+            FileResult::Content { data, truncated: false } if data == b"ij"
+        ));
+    }
+}
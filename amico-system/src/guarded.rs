@@ -0,0 +1,241 @@
+//! Permission checks at the tool boundary
+
+use crate::{Permission, ResourcePermission, SystemOperation, Tool};
+
+/// Error returned when an operation is not permitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionDenied(pub ResourcePermission);
+
+impl std::fmt::Display for PermissionDenied {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Permission denied: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for PermissionDenied {}
+
+/// Guarded tool error types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuardedError<E> {
+    /// The permission required by the input was not granted
+    PermissionDenied(PermissionDenied),
+    /// The inner tool failed
+    Tool(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for GuardedError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PermissionDenied(err) => write!(f, "{}", err),
+            Self::Tool(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for GuardedError<E> {}
+
+/// Tool wrapper that enforces a `Permission` before each call.
+///
+/// `required` maps the input to the permission it needs; the inner tool
+/// only runs if `permissions.check` grants it. [`Guarded::new`] uses
+/// `SystemOperation::required_permission`, so system tools need no
+/// mapping; [`Guarded::with_required`] accepts any other input type.
+///
+/// ```rust,ignore
+/// let files = Guarded::new(JailedFileOps::new("workspace")?, checker);
+/// let reader = Guarded::with_required(read_tool, checker, |path: &String| {
+///     ResourcePermission::FileRead(path.clone())
+/// });
+/// ```
+pub struct Guarded<T, P, F = fn(&<T as Tool>::Input) -> ResourcePermission> {
+    inner: T,
+    permissions: P,
+    required: F,
+}
+
+impl<T, P> Guarded<T, P>
+where
+    T: Tool,
+    T::Input: SystemOperation,
+{
+    /// Guard a system tool with the permission its operation requires
+    pub fn new(inner: T, permissions: P) -> Self {
+        Self {
+            inner,
+            permissions,
+            required: T::Input::required_permission,
+        }
+    }
+}
+
+impl<T, P, F> Guarded<T, P, F>
+where
+    T: Tool,
+    F: Fn(&T::Input) -> ResourcePermission,
+{
+    /// Guard `inner`, mapping each input to a permission with `required`
+    pub fn with_required(inner: T, permissions: P, required: F) -> Self {
+        Self {
+            inner,
+            permissions,
+            required,
+        }
+    }
+}
+
+impl<T, P, F> Guarded<T, P, F> {
+    /// Get a reference to the wrapped tool
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get a reference to the permission checker
+    pub fn permissions(&self) -> &P {
+        &self.permissions
+    }
+
+    /// Get a mutable reference to the permission checker, to grant or
+    /// revoke permissions
+    pub fn permissions_mut(&mut self) -> &mut P {
+        &mut self.permissions
+    }
+}
+
+impl<T, P, F> Tool for Guarded<T, P, F>
+where
+    T: Tool + Sync,
+    T::Input: Send,
+    P: Permission<ResourcePermission> + Sync,
+    F: Fn(&T::Input) -> ResourcePermission + Sync,
+{
+    type Input = T::Input;
+    type Output = T::Output;
+    type Error = GuardedError<T::Error>;
+
+    async fn execute(&self, input: T::Input) -> Result<T::Output, Self::Error> {
+        let permission = (self.required)(&input);
+        if !self.permissions.check(&permission) {
+            return Err(GuardedError::PermissionDenied(PermissionDenied(permission)));
+        }
+        self.inner.execute(input).await.map_err(GuardedError::Tool)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Option<&str> {
+        self.inner.input_schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FileOperation, FileResult, PermissionChecker};
+    use std::convert::Infallible;
+
+    /// Returns the length of the file name it is given
+    struct ReadFile;
+
+    impl Tool for ReadFile {
+        type Input = String;
+        type Output = usize;
+        type Error = Infallible;
+
+        async fn execute(&self, path: String) -> Result<usize, Infallible> {
+            Ok(path.len())
+        }
+
+        fn name(&self) -> &str {
+            "read_file"
+        }
+
+        fn description(&self) -> &str {
+            "Reads a file"
+        }
+    }
+
+    fn guarded() -> Guarded<ReadFile, PermissionChecker, impl Fn(&String) -> ResourcePermission> {
+        let mut checker = PermissionChecker::new();
+        checker.grant(ResourcePermission::FileRead("notes.txt".to_string()));
+        Guarded::with_required(ReadFile, checker, |path: &String| {
+            ResourcePermission::FileRead(path.clone())
+        })
+    }
+
+    #[tokio::test]
+    async fn test_guarded_tool_granted() {
+        let tool = guarded();
+        assert_eq!(tool.name(), "read_file");
+        assert_eq!(tool.execute("notes.txt".to_string()).await, Ok(9));
+    }
+
+    #[tokio::test]
+    async fn test_guarded_tool_denied() {
+        let mut tool = guarded();
+        let err = tool.execute("/etc/passwd".to_string()).await.unwrap_err();
+        assert_eq!(
+            err,
+            GuardedError::PermissionDenied(PermissionDenied(ResourcePermission::FileRead(
+                "/etc/passwd".to_string()
+            )))
+        );
+
+        tool.permissions_mut()
+            .revoke(&ResourcePermission::FileRead("notes.txt".to_string()));
+        assert!(tool.execute("notes.txt".to_string()).await.is_err());
+    }
+
+    /// Pretends to perform file operations
+    struct Touch;
+
+    impl Tool for Touch {
+        type Input = FileOperation;
+        type Output = FileResult;
+        type Error = Infallible;
+
+        async fn execute(&self, _input: FileOperation) -> Result<FileResult, Infallible> {
+            Ok(FileResult::Success)
+        }
+
+        fn name(&self) -> &str {
+            "file_ops"
+        }
+
+        fn description(&self) -> &str {
+            "Pretends to touch files"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_guarded_system_tool_uses_operation_permission() {
+        let mut checker = PermissionChecker::new();
+        checker.grant(ResourcePermission::FileRead("notes.txt".to_string()));
+        let tool = Guarded::new(Touch, checker);
+
+        let read = FileOperation::Read {
+            path: "notes.txt".to_string(),
+            max_bytes: None,
+        };
+        assert!(matches!(tool.execute(read).await, Ok(FileResult::Success)));
+
+        let err = tool
+            .execute(FileOperation::Write {
+                path: "notes.txt".to_string(),
+                content: vec![],
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            GuardedError::PermissionDenied(PermissionDenied(ResourcePermission::FileWrite(
+                "notes.txt".to_string()
+            )))
+        );
+    }
+}
@@ -0,0 +1,707 @@
+//! HTTP client tool (requires the `http` feature)
+
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{AUTHORIZATION, COOKIE, LOCATION, PROXY_AUTHORIZATION};
+use reqwest::{Method, StatusCode, Url};
+
+use crate::{NetworkOperation, NetworkResult, Permission, ResourcePermission, Tool};
+
+/// HTTP tool error types
+#[derive(Debug)]
+pub enum HttpToolError {
+    /// The permission set does not grant access to the target host
+    PermissionDenied(String),
+    /// The target host is, or resolves to, an address in a blocked range
+    BlockedAddress(String),
+    /// The HTTP client could not be built from the configuration
+    InvalidConfig(String),
+    /// The request could not be built (bad URL, method or header)
+    InvalidRequest(String),
+    /// The redirect chain exceeded the configured limit
+    TooManyRedirects(usize),
+    /// The response headers exceeded the configured size limit
+    HeadersTooLarge(usize),
+    /// The response body exceeded the configured size limit
+    ResponseTooLarge(usize),
+    /// The request did not complete within the configured timeout
+    Timeout,
+    /// The request failed at the transport level
+    RequestFailed(String),
+}
+
+impl std::fmt::Display for HttpToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PermissionDenied(host) => write!(f, "Network access denied: {}", host),
+            Self::BlockedAddress(msg) => write!(f, "Blocked network address: {}", msg),
+            Self::InvalidConfig(msg) => write!(f, "Invalid HTTP tool configuration: {}", msg),
+            Self::InvalidRequest(msg) => write!(f, "Invalid HTTP request: {}", msg),
+            Self::TooManyRedirects(max) => write!(f, "Too many redirects (max {})", max),
+            Self::HeadersTooLarge(max) => {
+                write!(f, "Response headers exceed {} bytes", max)
+            }
+            Self::ResponseTooLarge(max) => write!(f, "Response body exceeds {} bytes", max),
+            Self::Timeout => write!(f, "HTTP request timed out"),
+            Self::RequestFailed(msg) => write!(f, "HTTP request failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for HttpToolError {}
+
+impl From<reqwest::Error> for HttpToolError {
+    fn from(err: reqwest::Error) -> Self {
+        // Surface resolver rejections as `BlockedAddress` rather than a
+        // generic connection failure
+        let mut source = std::error::Error::source(&err);
+        while let Some(cause) = source {
+            if let Some(blocked) = cause.downcast_ref::<BlockedAddressError>() {
+                return Self::BlockedAddress(blocked.0.clone());
+            }
+            source = cause.source();
+        }
+
+        if err.is_timeout() {
+            Self::Timeout
+        } else if err.is_builder() {
+            Self::InvalidRequest(err.to_string())
+        } else {
+            Self::RequestFailed(err.to_string())
+        }
+    }
+}
+
+/// Range of IP addresses in CIDR notation (e.g. `10.0.0.0/8`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpRange {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    /// Create a range, returning `None` if the prefix is longer than the address
+    pub fn new(network: IpAddr, prefix_len: u8) -> Option<Self> {
+        let max_len = match network {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        (prefix_len <= max_len).then_some(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    /// Check whether an address falls inside this range.
+    ///
+    /// IPv4-mapped IPv6 addresses (`::ffff:a.b.c.d`) are matched as IPv4.
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        fn prefix(bits: u128, width: u32, len: u8) -> u128 {
+            if len == 0 {
+                0
+            } else {
+                bits >> (width - len as u32)
+            }
+        }
+
+        match (self.network, addr.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                prefix(u32::from(net).into(), 32, self.prefix_len)
+                    == prefix(u32::from(addr).into(), 32, self.prefix_len)
+            }
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                prefix(net.into(), 128, self.prefix_len)
+                    == prefix(addr.into(), 128, self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Error returned when parsing an invalid `IpRange`
+#[derive(Debug)]
+pub struct IpRangeParseError(String);
+
+impl std::fmt::Display for IpRangeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid IP range: {}", self.0)
+    }
+}
+
+impl std::error::Error for IpRangeParseError {}
+
+impl FromStr for IpRange {
+    type Err = IpRangeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || IpRangeParseError(s.to_string());
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, len)) => (addr, Some(len.parse::<u8>().map_err(|_| invalid())?)),
+            None => (s, None),
+        };
+        let network: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let prefix_len = prefix_len.unwrap_or(if network.is_ipv4() { 32 } else { 128 });
+
+        Self::new(network, prefix_len).ok_or_else(invalid)
+    }
+}
+
+/// Address ranges that are blocked by default: loopback, private,
+/// link-local, carrier-grade NAT and unspecified addresses
+fn default_blocked_ranges() -> Vec<IpRange> {
+    [
+        "0.0.0.0/8",
+        "10.0.0.0/8",
+        "100.64.0.0/10",
+        "127.0.0.0/8",
+        "169.254.0.0/16",
+        "172.16.0.0/12",
+        "192.168.0.0/16",
+        "::/128",
+        "::1/128",
+        "fc00::/7",
+        "fe80::/10",
+    ]
+    .iter()
+    .map(|range| range.parse().expect("default IP ranges are valid"))
+    .collect()
+}
+
+/// Configuration for `HttpTool`.
+///
+/// The defaults are safe for following model-provided URLs: internal
+/// address ranges are blocked and responses are size-limited.
+#[derive(Debug, Clone)]
+pub struct HttpToolConfig {
+    /// Timeout for each request, including reading the body
+    pub timeout: Duration,
+    /// Maximum number of redirects to follow
+    pub max_redirects: usize,
+    /// Maximum total size of the response headers in bytes
+    pub max_header_bytes: usize,
+    /// Maximum size of the response body in bytes
+    pub max_response_bytes: usize,
+    /// Address ranges the tool refuses to connect to, checked after DNS resolution
+    pub blocked_ranges: Vec<IpRange>,
+}
+
+impl Default for HttpToolConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_redirects: 5,
+            max_header_bytes: 64 * 1024,
+            max_response_bytes: 10 * 1024 * 1024,
+            blocked_ranges: default_blocked_ranges(),
+        }
+    }
+}
+
+fn is_blocked(ranges: &[IpRange], addr: &IpAddr) -> bool {
+    ranges.iter().any(|range| range.contains(addr))
+}
+
+/// Resolver error for hosts that resolve into a blocked range
+#[derive(Debug)]
+struct BlockedAddressError(String);
+
+impl std::fmt::Display for BlockedAddressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for BlockedAddressError {}
+
+/// DNS resolver that rejects hosts resolving into a blocked range
+struct GuardedResolver {
+    blocked_ranges: Arc<[IpRange]>,
+}
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let blocked_ranges = self.blocked_ranges.clone();
+
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<_> = tokio::net::lookup_host((host, 0)).await?.collect();
+
+            if let Some(addr) = addrs
+                .iter()
+                .find(|addr| is_blocked(&blocked_ranges, &addr.ip()))
+            {
+                let msg = format!("{} resolves to {}", host, addr.ip());
+                return Err(Box::new(BlockedAddressError(msg)) as _);
+            }
+
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// HTTP client tool performing `NetworkOperation::HttpRequest`s.
+///
+/// Every request, including each redirect hop, is checked against
+/// `ResourcePermission::NetworkAccess(host)` and the configured blocked
+/// address ranges before it is sent. Non-2xx responses are returned as a
+/// `NetworkResult` rather than an error, so the agent can inspect the
+/// status itself.
+///
+/// Proxies are never used, including those set through `HTTP_PROXY` or
+/// `HTTPS_PROXY`: a proxy resolves the host itself, which would bypass the
+/// blocked address check.
+pub struct HttpTool<P> {
+    client: reqwest::Client,
+    config: HttpToolConfig,
+    permissions: P,
+}
+
+impl<P> HttpTool<P> {
+    /// Create an HTTP tool with the default configuration
+    pub fn new(permissions: P) -> Result<Self, HttpToolError> {
+        Self::with_config(permissions, HttpToolConfig::default())
+    }
+
+    /// Create an HTTP tool with a custom request timeout
+    pub fn with_timeout(permissions: P, timeout: Duration) -> Result<Self, HttpToolError> {
+        Self::with_config(
+            permissions,
+            HttpToolConfig {
+                timeout,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Create an HTTP tool with a custom configuration
+    pub fn with_config(permissions: P, config: HttpToolConfig) -> Result<Self, HttpToolError> {
+        Self::with_builder(permissions, config, reqwest::Client::builder())
+    }
+
+    /// Build the client from `builder`, overriding every setting the
+    /// address checks rely on
+    fn with_builder(
+        permissions: P,
+        config: HttpToolConfig,
+        builder: reqwest::ClientBuilder,
+    ) -> Result<Self, HttpToolError> {
+        let resolver = GuardedResolver {
+            blocked_ranges: config.blocked_ranges.clone().into(),
+        };
+        // Redirects are followed manually so every hop is authorized
+        let client = builder
+            .timeout(config.timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(resolver))
+            .no_proxy()
+            .build()
+            .map_err(|err| HttpToolError::InvalidConfig(err.to_string()))?;
+
+        Ok(Self {
+            client,
+            config,
+            permissions,
+        })
+    }
+
+    /// Get the tool configuration
+    pub fn config(&self) -> &HttpToolConfig {
+        &self.config
+    }
+
+    /// Get the permissions used to authorize requests
+    pub fn permissions(&self) -> &P {
+        &self.permissions
+    }
+}
+
+impl<P> HttpTool<P>
+where
+    P: Permission<ResourcePermission>,
+{
+    /// Check that a URL's host is permitted and not a blocked IP literal
+    fn authorize(&self, url: &Url) -> Result<(), HttpToolError> {
+        let host = url
+            .host_str()
+            .ok_or_else(|| HttpToolError::InvalidRequest(format!("URL has no host: {}", url)))?;
+
+        if !self
+            .permissions
+            .check(&ResourcePermission::NetworkAccess(host.to_string()))
+        {
+            return Err(HttpToolError::PermissionDenied(host.to_string()));
+        }
+
+        // IP literals bypass the resolver, so check them here
+        let literal = host.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(addr) = literal.parse::<IpAddr>() {
+            if is_blocked(&self.config.blocked_ranges, &addr) {
+                return Err(HttpToolError::BlockedAddress(host.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn read_response(
+        &self,
+        mut response: reqwest::Response,
+    ) -> Result<NetworkResult, HttpToolError> {
+        let status = response.status().as_u16();
+
+        let header_bytes: usize = response
+            .headers()
+            .iter()
+            .map(|(name, value)| name.as_str().len() + value.len())
+            .sum();
+        if header_bytes > self.config.max_header_bytes {
+            return Err(HttpToolError::HeadersTooLarge(self.config.max_header_bytes));
+        }
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+
+        let limit = self.config.max_response_bytes;
+        if response
+            .content_length()
+            .is_some_and(|len| len > limit as u64)
+        {
+            return Err(HttpToolError::ResponseTooLarge(limit));
+        }
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if body.len() + chunk.len() > limit {
+                return Err(HttpToolError::ResponseTooLarge(limit));
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(NetworkResult {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+impl<P> Tool for HttpTool<P>
+where
+    P: Permission<ResourcePermission> + Sync,
+{
+    type Input = NetworkOperation;
+    type Output = NetworkResult;
+    type Error = HttpToolError;
+
+    async fn execute(&self, input: NetworkOperation) -> Result<NetworkResult, HttpToolError> {
+        let NetworkOperation::HttpRequest {
+            method,
+            url,
+            mut headers,
+            mut body,
+        } = input;
+
+        let mut url = Url::parse(&url)
+            .map_err(|err| HttpToolError::InvalidRequest(format!("{}: {}", url, err)))?;
+        let mut method = Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+            .map_err(|_| HttpToolError::InvalidRequest(format!("invalid method: {}", method)))?;
+        let mut redirects = 0;
+
+        loop {
+            self.authorize(&url)?;
+
+            let mut request = self.client.request(method.clone(), url.clone());
+            for (name, value) in &headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+            if let Some(body) = &body {
+                request = request.body(body.clone());
+            }
+
+            let response = request.send().await?;
+            let status = response.status();
+            let location = response.headers().get(LOCATION);
+
+            let Some(location) = location.filter(|_| status.is_redirection()) else {
+                return self.read_response(response).await;
+            };
+
+            if redirects == self.config.max_redirects {
+                return Err(HttpToolError::TooManyRedirects(self.config.max_redirects));
+            }
+            redirects += 1;
+
+            let location = location.to_str().map_err(|_| {
+                HttpToolError::InvalidRequest("redirect location is not valid UTF-8".to_string())
+            })?;
+            let next = url.join(location).map_err(|err| {
+                HttpToolError::InvalidRequest(format!("redirect to {}: {}", location, err))
+            })?;
+
+            // Don't leak credentials to a different host
+            if next.host_str() != url.host_str() {
+                headers.retain(|(name, _)| {
+                    ![AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION]
+                        .iter()
+                        .any(|sensitive| name.eq_ignore_ascii_case(sensitive.as_str()))
+                });
+            }
+
+            // 303, and 301/302 after a POST, switch to a body-less GET
+            if status == StatusCode::SEE_OTHER
+                || (matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND)
+                    && method == Method::POST)
+            {
+                method = Method::GET;
+                body = None;
+            }
+
+            url = next;
+        }
+    }
+
+    fn name(&self) -> &str {
+        "http_request"
+    }
+
+    fn description(&self) -> &str {
+        "Send an HTTP request and return the status, headers and body"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PermissionChecker;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serve a single canned response on a local port, returning the base URL
+    fn serve_once(response: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn redirect_to(location: &str) -> String {
+        format!(
+            "HTTP/1.1 302 Found\r\nlocation: {}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+            location
+        )
+    }
+
+    fn get(url: String) -> NetworkOperation {
+        NetworkOperation::HttpRequest {
+            method: "GET".to_string(),
+            url,
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    fn permissions(hosts: &[&str]) -> PermissionChecker {
+        let mut permissions = PermissionChecker::new();
+        for host in hosts {
+            permissions.grant(ResourcePermission::NetworkAccess(host.to_string()));
+        }
+        permissions
+    }
+
+    /// Config that allows the loopback mock server but blocks link-local
+    fn local_config() -> HttpToolConfig {
+        HttpToolConfig {
+            blocked_ranges: vec!["169.254.0.0/16".parse().unwrap()],
+            ..Default::default()
+        }
+    }
+
+    fn local_tool() -> HttpTool<PermissionChecker> {
+        HttpTool::with_config(permissions(&["127.0.0.1"]), local_config()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_success() {
+        let url = serve_once(
+            "HTTP/1.1 200 OK\r\ncontent-length: 5\r\nx-mock: yes\r\nconnection: close\r\n\r\nhello"
+                .to_string(),
+        );
+
+        let result = local_tool().execute(get(url)).await.unwrap();
+        assert_eq!(result.status, 200);
+        assert_eq!(result.body, b"hello");
+        assert!(result
+            .headers
+            .contains(&("x-mock".to_string(), "yes".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_returns_non_2xx() {
+        let url = serve_once(
+            "HTTP/1.1 404 Not Found\r\ncontent-length: 7\r\nconnection: close\r\n\r\nmissing"
+                .to_string(),
+        );
+
+        let result = local_tool().execute(get(url)).await.unwrap();
+        assert_eq!(result.status, 404);
+        assert_eq!(result.body, b"missing");
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_permission_denied() {
+        let tool = HttpTool::with_config(PermissionChecker::new(), local_config()).unwrap();
+
+        let err = tool
+            .execute(get("http://127.0.0.1:9/".to_string()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HttpToolError::PermissionDenied(host) if host == "127.0.0.1"));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_timeout() {
+        // Accept the connection but never respond
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let config = HttpToolConfig {
+            timeout: Duration::from_millis(100),
+            ..local_config()
+        };
+        let tool = HttpTool::with_config(permissions(&["127.0.0.1"]), config).unwrap();
+
+        let err = tool.execute(get(url)).await.unwrap_err();
+        assert!(matches!(err, HttpToolError::Timeout));
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_follows_redirect() {
+        let target = serve_once(
+            "HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok".to_string(),
+        );
+        let url = serve_once(redirect_to(&format!("{}/final", target)));
+
+        let result = local_tool().execute(get(url)).await.unwrap();
+        assert_eq!(result.status, 200);
+        assert_eq!(result.body, b"ok");
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_redirect_limit() {
+        let url = serve_once(redirect_to("/elsewhere"));
+        let config = HttpToolConfig {
+            max_redirects: 0,
+            ..local_config()
+        };
+        let tool = HttpTool::with_config(permissions(&["127.0.0.1"]), config).unwrap();
+
+        let err = tool.execute(get(url)).await.unwrap_err();
+        assert!(matches!(err, HttpToolError::TooManyRedirects(0)));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_blocks_link_local_redirect() {
+        let url = serve_once(redirect_to("http://169.254.169.254/latest/meta-data"));
+        // Both hosts are permitted, so only the address check can stop it
+        let tool = HttpTool::with_config(
+            permissions(&["127.0.0.1", "169.254.169.254"]),
+            local_config(),
+        )
+        .unwrap();
+
+        let err = tool.execute(get(url)).await.unwrap_err();
+        assert!(matches!(err, HttpToolError::BlockedAddress(host) if host == "169.254.169.254"));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_blocks_resolved_address() {
+        // `localhost` resolves to loopback, which the default config blocks
+        let tool = HttpTool::new(permissions(&["localhost"])).unwrap();
+
+        let err = tool
+            .execute(get("http://localhost:9/".to_string()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HttpToolError::BlockedAddress(_)));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_ignores_proxy() {
+        // A proxy would resolve `localhost` itself and answer for it. The
+        // builder stands in for one configured through `HTTP_PROXY`, without
+        // touching the process environment.
+        let proxy = serve_once(
+            "HTTP/1.1 200 OK\r\ncontent-length: 7\r\nconnection: close\r\n\r\nproxied".to_string(),
+        );
+        let builder = reqwest::Client::builder().proxy(reqwest::Proxy::all(&proxy).unwrap());
+        let tool = HttpTool::with_builder(
+            permissions(&["localhost"]),
+            HttpToolConfig::default(),
+            builder,
+        );
+
+        let err = tool
+            .unwrap()
+            .execute(get("http://localhost:9/".to_string()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, HttpToolError::BlockedAddress(_)));
+    }
+
+    #[tokio::test]
+    async fn test_http_tool_rejects_oversized_body() {
+        let url = serve_once(
+            "HTTP/1.1 200 OK\r\ncontent-length: 11\r\nconnection: close\r\n\r\nhello world"
+                .to_string(),
+        );
+        let config = HttpToolConfig {
+            max_response_bytes: 5,
+            ..local_config()
+        };
+        let tool = HttpTool::with_config(permissions(&["127.0.0.1"]), config).unwrap();
+
+        let err = tool.execute(get(url)).await.unwrap_err();
+        assert!(matches!(err, HttpToolError::ResponseTooLarge(5)));
+    }
+
+    #[test]
+    fn test_ip_range_contains() {
+        let range: IpRange = "10.0.0.0/8".parse().unwrap();
+        assert!(range.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(!range.contains(&"11.0.0.1".parse().unwrap()));
+        // IPv4-mapped IPv6 addresses match their IPv4 range
+        assert!(range.contains(&"::ffff:10.0.0.1".parse().unwrap()));
+
+        let range: IpRange = "fe80::/10".parse().unwrap();
+        assert!(range.contains(&"fe80::1".parse().unwrap()));
+        assert!(!range.contains(&"2001:db8::1".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<IpRange>().is_err());
+        assert!("not-an-ip/8".parse::<IpRange>().is_err());
+    }
+}
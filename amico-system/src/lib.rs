@@ -32,6 +32,37 @@
 //! ```
 
 use std::future::Future;
+use std::time::Duration;
+
+mod denied;
+pub use denied::{Denied, DeniedFileOps, DeniedNetworkOps, DeniedProcessOps, DeniedSystem};
+
+mod guarded;
+pub use guarded::{Guarded, GuardedError, PermissionDenied};
+
+mod observable;
+pub use observable::{ObservableExt, Sample, SampleStream, Throttle, ThrottleStream};
+
+mod stream;
+pub use stream::{Filter, Map, StreamExt};
+
+mod vad;
+pub use vad::{rms_energy, Utterance, VadConfig, VadGate};
+
+#[cfg(feature = "fs")]
+mod fs;
+#[cfg(feature = "fs")]
+pub use fs::{FileToolError, JailedFileOps, LocalFileOps};
+
+#[cfg(feature = "process")]
+mod process;
+#[cfg(feature = "process")]
+pub use process::{LocalProcessOps, ProcessChunk, ProcessToolError};
+
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::{HttpTool, HttpToolConfig, HttpToolError, IpRange, IpRangeParseError};
 
 /// Core tool trait - all tools implement this
 pub trait Tool {
@@ -129,21 +160,93 @@ pub trait System {
     fn file_ops(&self) -> &Self::FileOps;
     fn network_ops(&self) -> &Self::NetworkOps;
     fn process_ops(&self) -> &Self::ProcessOps;
+
+    /// Operations this platform supports.
+    ///
+    /// Restricted platforms (browsers, embedded devices) override this so
+    /// agents can degrade gracefully instead of calling a tool that always
+    /// fails. Defaults to everything supported.
+    fn capabilities(&self) -> SystemCapabilities {
+        SystemCapabilities::all()
+    }
+}
+
+/// Operations supported by a `System`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemCapabilities {
+    pub file: bool,
+    pub network: bool,
+    pub process: bool,
+}
+
+impl SystemCapabilities {
+    /// Every operation supported
+    pub fn all() -> Self {
+        Self {
+            file: true,
+            network: true,
+            process: true,
+        }
+    }
+
+    /// No operation supported
+    pub fn none() -> Self {
+        Self {
+            file: false,
+            network: false,
+            process: false,
+        }
+    }
 }
 
 /// File system operations
 #[derive(Debug, Clone)]
 pub enum FileOperation {
-    Read { path: String },
-    Write { path: String, content: Vec<u8> },
-    Delete { path: String },
-    List { path: String },
+    Read {
+        path: String,
+        /// Maximum number of bytes to read; longer files are truncated
+        max_bytes: Option<usize>,
+    },
+    /// Read up to `max_bytes` starting at `offset`, for paging through
+    /// large files
+    ReadChunk {
+        path: String,
+        offset: u64,
+        max_bytes: usize,
+    },
+    Write {
+        path: String,
+        content: Vec<u8>,
+    },
+    Delete {
+        path: String,
+    },
+    List {
+        path: String,
+    },
+}
+
+impl FileOperation {
+    /// The path this operation acts on
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Read { path, .. }
+            | Self::ReadChunk { path, .. }
+            | Self::Write { path, .. }
+            | Self::Delete { path }
+            | Self::List { path } => path,
+        }
+    }
 }
 
 /// File system result
 #[derive(Debug, Clone)]
 pub enum FileResult {
-    Content(Vec<u8>),
+    Content {
+        data: Vec<u8>,
+        /// More data follows what was read
+        truncated: bool,
+    },
     Success,
     Listing(Vec<String>),
 }
@@ -174,6 +277,11 @@ pub enum ProcessOperation {
         command: String,
         args: Vec<String>,
         env: Vec<(String, String)>,
+        /// Maximum run time; `LocalProcessOps` kills the process group once
+        /// it expires
+        timeout: Option<Duration>,
+        /// Maximum number of bytes captured across stdout and stderr
+        max_output_bytes: Option<usize>,
     },
 }
 
@@ -183,6 +291,10 @@ pub struct ProcessResult {
     pub exit_code: i32,
     pub stdout: Vec<u8>,
     pub stderr: Vec<u8>,
+    /// Output exceeded `max_output_bytes` and was cut off
+    pub truncated: bool,
+    /// The process was killed because it exceeded `timeout`
+    pub timed_out: bool,
 }
 
 /// Permission types for system resources
@@ -194,6 +306,63 @@ pub enum ResourcePermission {
     ProcessExecution,
 }
 
+/// Operations performed through a `System` tool
+pub trait SystemOperation {
+    /// Name of the tool kind that performs this operation
+    const KIND: &'static str;
+
+    /// Permission needed to perform this operation
+    fn required_permission(&self) -> ResourcePermission;
+}
+
+impl SystemOperation for FileOperation {
+    const KIND: &'static str = "file_ops";
+
+    fn required_permission(&self) -> ResourcePermission {
+        match self {
+            Self::Read { path, .. } | Self::ReadChunk { path, .. } | Self::List { path } => {
+                ResourcePermission::FileRead(path.clone())
+            }
+            Self::Write { path, .. } | Self::Delete { path } => {
+                ResourcePermission::FileWrite(path.clone())
+            }
+        }
+    }
+}
+
+impl SystemOperation for NetworkOperation {
+    const KIND: &'static str = "network_ops";
+
+    fn required_permission(&self) -> ResourcePermission {
+        match self {
+            Self::HttpRequest { url, .. } => ResourcePermission::NetworkAccess(url_host(url)),
+        }
+    }
+}
+
+impl SystemOperation for ProcessOperation {
+    const KIND: &'static str = "process_ops";
+
+    fn required_permission(&self) -> ResourcePermission {
+        ResourcePermission::ProcessExecution
+    }
+}
+
+/// Host of a URL as `HttpTool` checks it: lowercased, without scheme,
+/// credentials or port, and with IPv6 addresses kept in brackets
+fn url_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host_port = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host_port)| host_port);
+    let host = match host_port.find(']') {
+        Some(end) if host_port.starts_with('[') => &host_port[..=end],
+        _ => host_port.split(':').next().unwrap_or_default(),
+    };
+    host.to_ascii_lowercase()
+}
+
 /// Simple permission checker
 #[derive(Debug, Clone, Default)]
 pub struct PermissionChecker {
@@ -228,3 +397,86 @@ impl Permission<ResourcePermission> for PermissionChecker {
         self.granted.retain(|r| r != resource);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    /// Tool that does nothing
+    struct Noop;
+
+    impl Tool for Noop {
+        type Input = ();
+        type Output = ();
+        type Error = Infallible;
+
+        async fn execute(&self, _input: ()) -> Result<(), Infallible> {
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "noop"
+        }
+
+        fn description(&self) -> &str {
+            "Does nothing"
+        }
+    }
+
+    /// System for a device with a local file system and no network
+    struct Offline;
+
+    impl System for Offline {
+        type FileOps = Noop;
+        type NetworkOps = Noop;
+        type ProcessOps = Noop;
+
+        fn file_ops(&self) -> &Noop {
+            &Noop
+        }
+
+        fn network_ops(&self) -> &Noop {
+            &Noop
+        }
+
+        fn process_ops(&self) -> &Noop {
+            &Noop
+        }
+
+        fn capabilities(&self) -> SystemCapabilities {
+            SystemCapabilities {
+                network: false,
+                ..SystemCapabilities::all()
+            }
+        }
+    }
+
+    #[test]
+    fn test_network_permission_uses_host() {
+        let request = |url: &str| NetworkOperation::HttpRequest {
+            method: "GET".to_string(),
+            url: url.to_string(),
+            headers: vec![],
+            body: None,
+        };
+        let host = |url| match request(url).required_permission() {
+            ResourcePermission::NetworkAccess(host) => host,
+            other => panic!("unexpected permission {:?}", other),
+        };
+
+        assert_eq!(host("https://api.example.com/v1?q=1"), "api.example.com");
+        assert_eq!(host("http://user:pw@Example.COM:8080#top"), "example.com");
+        assert_eq!(host("http://[::1]:3000/"), "[::1]");
+    }
+
+    #[test]
+    fn test_system_reports_network_unavailable() {
+        let capabilities = Offline.capabilities();
+        assert!(capabilities.file);
+        assert!(capabilities.process);
+        assert!(!capabilities.network);
+        assert_ne!(capabilities, SystemCapabilities::all());
+        assert_ne!(capabilities, SystemCapabilities::none());
+    }
+}
@@ -0,0 +1,194 @@
+//! Rate-shaping combinators for `Observable`s
+
+use std::time::Duration;
+
+use amico_runtime::Monotonic;
+
+use crate::{Observable, Stream};
+
+/// Extension methods for shaping the rate of an `Observable`.
+///
+/// Useful when a sensor emits faster than the agent can process.
+pub trait ObservableExt: Observable + Sized {
+    /// Emit at most one event per `interval`, dropping events in between
+    fn throttle(self, interval: Duration) -> Throttle<Self> {
+        Throttle {
+            source: self,
+            interval,
+        }
+    }
+
+    /// Emit the latest event once per `interval`, discarding older ones
+    fn sample(self, interval: Duration) -> Sample<Self> {
+        Sample {
+            source: self,
+            interval,
+        }
+    }
+}
+
+impl<O: Observable> ObservableExt for O {}
+
+/// Observable returned by [`ObservableExt::throttle`]
+pub struct Throttle<O> {
+    source: O,
+    interval: Duration,
+}
+
+impl<O: Observable> Observable for Throttle<O> {
+    type Event = O::Event;
+    type Stream = ThrottleStream<O::Stream>;
+
+    fn subscribe(&self) -> Self::Stream {
+        ThrottleStream {
+            inner: self.source.subscribe(),
+            interval: self.interval,
+            last_emitted: None,
+        }
+    }
+}
+
+/// Stream that passes through the first event of each interval
+pub struct ThrottleStream<S> {
+    inner: S,
+    interval: Duration,
+    last_emitted: Option<Monotonic>,
+}
+
+impl<S: Stream> Stream for ThrottleStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.inner.poll_next() {
+            if self
+                .last_emitted
+                .is_none_or(|last| last.elapsed() >= self.interval)
+            {
+                self.last_emitted = Some(Monotonic::start());
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+/// Observable returned by [`ObservableExt::sample`]
+pub struct Sample<O> {
+    source: O,
+    interval: Duration,
+}
+
+impl<O: Observable> Observable for Sample<O> {
+    type Event = O::Event;
+    type Stream = SampleStream<O::Stream>;
+
+    fn subscribe(&self) -> Self::Stream {
+        SampleStream {
+            inner: self.source.subscribe(),
+            interval: self.interval,
+            last_tick: None,
+            latest: None,
+        }
+    }
+}
+
+/// Stream that yields the most recent event once per interval
+pub struct SampleStream<S: Stream> {
+    inner: S,
+    interval: Duration,
+    last_tick: Option<Monotonic>,
+    latest: Option<S::Item>,
+}
+
+impl<S: Stream> Stream for SampleStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(&mut self) -> Option<Self::Item> {
+        while let Some(item) = self.inner.poll_next() {
+            self.latest = Some(item);
+        }
+
+        let due = self
+            .last_tick
+            .is_none_or(|last| last.elapsed() >= self.interval);
+        if due && self.latest.is_some() {
+            self.last_tick = Some(Monotonic::start());
+            return self.latest.take();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::VecDeque;
+    use std::rc::Rc;
+    use std::thread;
+
+    const INTERVAL: Duration = Duration::from_millis(50);
+
+    /// Fake sensor whose events are pushed by the test
+    #[derive(Default)]
+    struct FakeSensor {
+        queue: Rc<RefCell<VecDeque<u32>>>,
+    }
+
+    struct FakeStream {
+        queue: Rc<RefCell<VecDeque<u32>>>,
+    }
+
+    impl Stream for FakeStream {
+        type Item = u32;
+
+        fn poll_next(&mut self) -> Option<u32> {
+            self.queue.borrow_mut().pop_front()
+        }
+    }
+
+    impl Observable for FakeSensor {
+        type Event = u32;
+        type Stream = FakeStream;
+
+        fn subscribe(&self) -> FakeStream {
+            FakeStream {
+                queue: self.queue.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_throttle_drops_burst() {
+        let sensor = FakeSensor::default();
+        let queue = sensor.queue.clone();
+        let mut stream = sensor.throttle(INTERVAL).subscribe();
+
+        queue.borrow_mut().extend(1..=5);
+        assert_eq!(stream.poll_next(), Some(1));
+        assert_eq!(stream.poll_next(), None);
+        assert!(queue.borrow().is_empty());
+
+        thread::sleep(INTERVAL + Duration::from_millis(10));
+        queue.borrow_mut().extend(6..=7);
+        assert_eq!(stream.poll_next(), Some(6));
+        assert_eq!(stream.poll_next(), None);
+    }
+
+    #[test]
+    fn test_sample_emits_latest() {
+        let sensor = FakeSensor::default();
+        let queue = sensor.queue.clone();
+        let mut stream = sensor.sample(INTERVAL).subscribe();
+
+        queue.borrow_mut().extend(1..=3);
+        assert_eq!(stream.poll_next(), Some(3));
+
+        queue.borrow_mut().extend(4..=5);
+        assert_eq!(stream.poll_next(), None);
+
+        thread::sleep(INTERVAL + Duration::from_millis(10));
+        assert_eq!(stream.poll_next(), Some(5));
+        assert_eq!(stream.poll_next(), None);
+    }
+}
@@ -0,0 +1,526 @@
+//! Local process execution tool (requires the `process` feature)
+
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
+use tokio::process::{Child, Command};
+use tokio::time::Instant;
+
+use crate::{ProcessOperation, ProcessResult, Tool};
+
+/// Process tool error types
+#[derive(Debug)]
+pub enum ProcessToolError {
+    /// The process could not be started
+    Spawn(std::io::Error),
+    /// Reading output from or waiting on the process failed
+    Io(std::io::Error),
+}
+
+impl std::fmt::Display for ProcessToolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Spawn(err) => write!(f, "Failed to start process: {}", err),
+            Self::Io(err) => write!(f, "Process I/O failed: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ProcessToolError {}
+
+impl From<std::io::Error> for ProcessToolError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Output budget shared by stdout and stderr
+struct OutputBudget {
+    remaining: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+impl OutputBudget {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            remaining: AtomicUsize::new(max_bytes),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    /// Reserve up to `wanted` bytes, returning how many may be kept
+    fn take(&self, wanted: usize) -> usize {
+        let previous = self
+            .remaining
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                Some(remaining.saturating_sub(wanted))
+            })
+            .unwrap_or(0);
+        let granted = previous.min(wanted);
+        if granted < wanted {
+            self.exceeded.store(true, Ordering::Relaxed);
+        }
+        granted
+    }
+}
+
+/// Read `reader` to the end, keeping only what the budget allows.
+///
+/// Output past the budget is drained and discarded so the process does not
+/// block on a full pipe.
+async fn capture<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    buf: &mut Vec<u8>,
+    budget: &OutputBudget,
+) -> std::io::Result<()> {
+    let Some(mut reader) = reader else {
+        return Ok(());
+    };
+    let mut chunk = [0u8; 8192];
+    loop {
+        let n = reader.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(());
+        }
+        let keep = budget.take(n);
+        buf.extend_from_slice(&chunk[..keep]);
+    }
+}
+
+/// Longest line yielded by [`LocalProcessOps::stream`]; longer lines are
+/// split into several chunks
+const MAX_LINE_BYTES: u64 = 8192;
+
+/// A piece of output from a streamed process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProcessChunk {
+    /// A line written to stdout, including its trailing newline
+    Stdout(Vec<u8>),
+    /// A line written to stderr, including its trailing newline
+    Stderr(Vec<u8>),
+    /// The process exited or was killed; always the last chunk
+    Finished {
+        exit_code: i32,
+        /// Output exceeded `max_output_bytes` and was cut off
+        truncated: bool,
+        /// The process was killed because it exceeded `timeout`
+        timed_out: bool,
+    },
+}
+
+/// Stream the lines of `reader`, tagging each with `chunk`
+fn lines<R>(
+    reader: Option<R>,
+    chunk: fn(Vec<u8>) -> ProcessChunk,
+) -> impl futures::Stream<Item = std::io::Result<ProcessChunk>> + Send
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    stream::unfold(reader.map(BufReader::new), move |reader| async move {
+        let mut reader = reader?;
+        let mut line = Vec::new();
+        match (&mut reader)
+            .take(MAX_LINE_BYTES)
+            .read_until(b'\n', &mut line)
+            .await
+        {
+            Ok(0) => None,
+            Ok(_) => Some((Ok(chunk(line)), Some(reader))),
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// State of a process started by [`LocalProcessOps::stream`]
+struct Streaming {
+    child: Child,
+    output: BoxStream<'static, std::io::Result<ProcessChunk>>,
+    budget: OutputBudget,
+    deadline: Option<Instant>,
+}
+
+impl Streaming {
+    /// Wait for the next chunk to yield, ending with `Finished`
+    async fn next_chunk(&mut self) -> Result<ProcessChunk, ProcessToolError> {
+        loop {
+            let next = match self.deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, self.output.next()).await,
+                None => Ok(self.output.next().await),
+            };
+            let mut line = match next {
+                Ok(Some(chunk)) => chunk?,
+                Ok(None) => return self.finish().await,
+                Err(_) => return self.kill().await,
+            };
+            // Output past the budget is still drained so the process does
+            // not block on a full pipe
+            let keep = match &mut line {
+                ProcessChunk::Stdout(data) | ProcessChunk::Stderr(data) => {
+                    let keep = self.budget.take(data.len());
+                    data.truncate(keep);
+                    keep
+                }
+                ProcessChunk::Finished { .. } => return Ok(line),
+            };
+            if keep > 0 {
+                return Ok(line);
+            }
+        }
+    }
+
+    /// Wait for the process to exit once its output is closed
+    async fn finish(&mut self) -> Result<ProcessChunk, ProcessToolError> {
+        let status = match self.deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, self.child.wait()).await {
+                Ok(status) => status?,
+                Err(_) => return self.kill().await,
+            },
+            None => self.child.wait().await?,
+        };
+        Ok(self.finished(status.code().unwrap_or(-1), false))
+    }
+
+    /// Kill the process group after the timeout expired
+    async fn kill(&mut self) -> Result<ProcessChunk, ProcessToolError> {
+        kill_process_group(&self.child);
+        self.child.kill().await?;
+        Ok(self.finished(-1, true))
+    }
+
+    fn finished(&self, exit_code: i32, timed_out: bool) -> ProcessChunk {
+        ProcessChunk::Finished {
+            exit_code,
+            truncated: self.budget.exceeded.load(Ordering::Relaxed),
+            timed_out,
+        }
+    }
+}
+
+/// Start `command` in its own process group with piped output
+fn spawn(command: String, args: Vec<String>, env: Vec<(String, String)>) -> std::io::Result<Child> {
+    let mut cmd = Command::new(command);
+    cmd.args(args)
+        .envs(env)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    #[cfg(unix)]
+    cmd.process_group(0);
+    cmd.spawn()
+}
+
+/// Kill the child's whole process group, so grandchildren die with it
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    if let Some(pid) = child.id() {
+        // SAFETY: killpg only sends a signal; the child was spawned as the
+        // leader of its own group, so its pid is the group id
+        unsafe {
+            libc::killpg(pid as libc::pid_t, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_child: &Child) {}
+
+/// Tool that runs processes on the local machine.
+///
+/// Enforces the limits of `ProcessOperation::Execute`: once `timeout`
+/// expires the process group is killed (on Unix, each process runs in its
+/// own group) and `timed_out` is set; output beyond `max_output_bytes`
+/// across stdout and stderr is discarded and `truncated` is set. Killed
+/// processes report an exit code of -1.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalProcessOps;
+
+impl LocalProcessOps {
+    /// Start a process and stream its output line by line as it arrives.
+    ///
+    /// Applies the same limits as `execute`: output beyond
+    /// `max_output_bytes` is dropped, so at most that many bytes are ever
+    /// yielded, and the process group is killed once `timeout` expires.
+    /// The stream ends with a single [`ProcessChunk::Finished`]; dropping
+    /// it early kills the process. Must be called within a Tokio runtime.
+    pub fn stream(
+        &self,
+        operation: ProcessOperation,
+    ) -> Result<BoxStream<'static, Result<ProcessChunk, ProcessToolError>>, ProcessToolError> {
+        let ProcessOperation::Execute {
+            command,
+            args,
+            env,
+            timeout,
+            max_output_bytes,
+        } = operation;
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut child = spawn(command, args, env).map_err(ProcessToolError::Spawn)?;
+        let output = stream::select(
+            lines(child.stdout.take(), ProcessChunk::Stdout),
+            lines(child.stderr.take(), ProcessChunk::Stderr),
+        )
+        .boxed();
+        let state = Streaming {
+            child,
+            output,
+            budget: OutputBudget::new(max_output_bytes.unwrap_or(usize::MAX)),
+            deadline,
+        };
+
+        Ok(stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.next_chunk().await {
+                Ok(chunk @ ProcessChunk::Finished { .. }) => Some((Ok(chunk), None)),
+                Ok(chunk) => Some((Ok(chunk), Some(state))),
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+        .boxed())
+    }
+}
+
+impl Tool for LocalProcessOps {
+    type Input = ProcessOperation;
+    type Output = ProcessResult;
+    type Error = ProcessToolError;
+
+    async fn execute(
+        &self,
+        operation: ProcessOperation,
+    ) -> Result<ProcessResult, ProcessToolError> {
+        let ProcessOperation::Execute {
+            command,
+            args,
+            env,
+            timeout,
+            max_output_bytes,
+        } = operation;
+
+        let mut child = spawn(command, args, env).map_err(ProcessToolError::Spawn)?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+        let budget = OutputBudget::new(max_output_bytes.unwrap_or(usize::MAX));
+        let mut stdout_buf = Vec::new();
+        let mut stderr_buf = Vec::new();
+
+        let status = {
+            let run = async {
+                let (out, err, status) = futures::join!(
+                    capture(stdout, &mut stdout_buf, &budget),
+                    capture(stderr, &mut stderr_buf, &budget),
+                    child.wait(),
+                );
+                out?;
+                err?;
+                status
+            };
+            match timeout {
+                Some(timeout) => tokio::time::timeout(timeout, run).await.ok(),
+                None => Some(run.await),
+            }
+        };
+
+        let (exit_code, timed_out) = match status {
+            Some(status) => (status?.code().unwrap_or(-1), false),
+            None => {
+                kill_process_group(&child);
+                child.kill().await?;
+                (-1, true)
+            }
+        };
+
+        Ok(ProcessResult {
+            exit_code,
+            stdout: stdout_buf,
+            stderr: stderr_buf,
+            truncated: budget.exceeded.load(Ordering::Relaxed),
+            timed_out,
+        })
+    }
+
+    fn name(&self) -> &str {
+        "process_ops"
+    }
+
+    fn description(&self) -> &str {
+        "Run a local process with a timeout and output limit"
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sh(
+        script: &str,
+        timeout: Option<Duration>,
+        max_output_bytes: Option<usize>,
+    ) -> ProcessOperation {
+        ProcessOperation::Execute {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            env: vec![],
+            timeout,
+            max_output_bytes,
+        }
+    }
+
+    /// Whether `pid` is running; killed processes left unreaped as zombies
+    /// (in containers without an init process) count as dead
+    fn is_alive(pid: &str) -> bool {
+        match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            // The state follows the parenthesized command name
+            Ok(stat) => !stat
+                .rsplit_once(") ")
+                .is_some_and(|(_, rest)| rest.starts_with('Z')),
+            Err(_) => std::process::Command::new("kill")
+                .args(["-0", pid])
+                .status()
+                .unwrap()
+                .success(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_runs_to_completion() {
+        let result = LocalProcessOps
+            .execute(sh("echo out; echo err >&2; exit 3", None, None))
+            .await
+            .unwrap();
+        assert_eq!(result.exit_code, 3);
+        assert_eq!(result.stdout, b"out\n");
+        assert_eq!(result.stderr, b"err\n");
+        assert!(!result.truncated);
+        assert!(!result.timed_out);
+    }
+
+    #[tokio::test]
+    async fn test_process_timeout_kills_group() {
+        let start = Instant::now();
+        // The background sleep is a grandchild; it must die with the group
+        let result = LocalProcessOps
+            .execute(sh(
+                "sleep 30 & echo $!; wait",
+                Some(Duration::from_millis(300)),
+                None,
+            ))
+            .await
+            .unwrap();
+        assert!(result.timed_out);
+        assert_eq!(result.exit_code, -1);
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        let pid = String::from_utf8(result.stdout).unwrap();
+        let pid = pid.trim();
+        assert!(!pid.is_empty());
+        // Give the kernel a moment to reap the killed grandchild
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!is_alive(pid));
+    }
+
+    #[tokio::test]
+    async fn test_process_output_cap() {
+        let result = LocalProcessOps
+            .execute(sh(
+                "head -c 100000 /dev/zero; head -c 100000 /dev/zero >&2",
+                None,
+                Some(1000),
+            ))
+            .await
+            .unwrap();
+        assert!(result.truncated);
+        assert_eq!(result.stdout.len() + result.stderr.len(), 1000);
+        assert_eq!(result.exit_code, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_lines_as_they_arrive() {
+        let start = Instant::now();
+        let mut stream = LocalProcessOps
+            .stream(sh(
+                "echo one; sleep 0.5; echo two >&2; sleep 0.5; echo three",
+                None,
+                None,
+            ))
+            .unwrap();
+
+        assert_eq!(
+            stream.next().await.unwrap().unwrap(),
+            ProcessChunk::Stdout(b"one\n".to_vec())
+        );
+        // The first line arrives long before the process exits
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        let rest: Vec<_> = stream.map(Result::unwrap).collect().await;
+        assert_eq!(
+            rest,
+            vec![
+                ProcessChunk::Stderr(b"two\n".to_vec()),
+                ProcessChunk::Stdout(b"three\n".to_vec()),
+                ProcessChunk::Finished {
+                    exit_code: 0,
+                    truncated: false,
+                    timed_out: false,
+                },
+            ]
+        );
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_stream_output_cap() {
+        let chunks: Vec<_> = LocalProcessOps
+            .stream(sh("yes line | head -n 10000", None, Some(12)))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(
+            chunks,
+            vec![
+                ProcessChunk::Stdout(b"line\n".to_vec()),
+                ProcessChunk::Stdout(b"line\n".to_vec()),
+                ProcessChunk::Stdout(b"li".to_vec()),
+                ProcessChunk::Finished {
+                    exit_code: 0,
+                    truncated: true,
+                    timed_out: false,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_timeout() {
+        let start = Instant::now();
+        let chunks: Vec<_> = LocalProcessOps
+            .stream(sh(
+                "echo started; sleep 30",
+                Some(Duration::from_millis(300)),
+                None,
+            ))
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(
+            chunks,
+            vec![
+                ProcessChunk::Stdout(b"started\n".to_vec()),
+                ProcessChunk::Finished {
+                    exit_code: -1,
+                    truncated: false,
+                    timed_out: true,
+                },
+            ]
+        );
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
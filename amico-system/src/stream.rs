@@ -0,0 +1,116 @@
+//! Lazy adapters for `Stream`s
+
+use crate::Stream;
+
+/// Extension methods for transforming a `Stream` without collecting it.
+///
+/// For example, an event source can map raw sensor bytes into typed
+/// events as they are polled.
+pub trait StreamExt: Stream + Sized {
+    /// Transform each item with `f`
+    fn map<F, T>(self, f: F) -> Map<Self, F>
+    where
+        F: FnMut(Self::Item) -> T,
+    {
+        Map { inner: self, f }
+    }
+
+    /// Yield only the items for which `predicate` returns `true`
+    fn filter<F>(self, predicate: F) -> Filter<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+    {
+        Filter {
+            inner: self,
+            predicate,
+        }
+    }
+}
+
+impl<S: Stream> StreamExt for S {}
+
+/// Stream returned by [`StreamExt::map`]
+pub struct Map<S, F> {
+    inner: S,
+    f: F,
+}
+
+impl<S, F, T> Stream for Map<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> T,
+{
+    type Item = T;
+
+    fn poll_next(&mut self) -> Option<T> {
+        self.inner.poll_next().map(&mut self.f)
+    }
+}
+
+/// Stream returned by [`StreamExt::filter`]
+pub struct Filter<S, F> {
+    inner: S,
+    predicate: F,
+}
+
+impl<S, F> Stream for Filter<S, F>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> bool,
+{
+    type Item = S::Item;
+
+    fn poll_next(&mut self) -> Option<S::Item> {
+        while let Some(item) = self.inner.poll_next() {
+            if (self.predicate)(&item) {
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stream over a fixed list of raw sensor readings
+    struct RawReadings(std::vec::IntoIter<Vec<u8>>);
+
+    impl Stream for RawReadings {
+        type Item = Vec<u8>;
+
+        fn poll_next(&mut self) -> Option<Vec<u8>> {
+            self.0.next()
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Reading {
+        celsius: u8,
+    }
+
+    fn readings(raw: Vec<Vec<u8>>) -> RawReadings {
+        RawReadings(raw.into_iter())
+    }
+
+    #[test]
+    fn test_map_transforms_type() {
+        let mut stream = readings(vec![vec![20], vec![21]]).map(|raw| Reading { celsius: raw[0] });
+
+        assert_eq!(stream.poll_next(), Some(Reading { celsius: 20 }));
+        assert_eq!(stream.poll_next(), Some(Reading { celsius: 21 }));
+        assert_eq!(stream.poll_next(), None);
+    }
+
+    #[test]
+    fn test_filter_skips_non_matching() {
+        let mut stream = readings(vec![vec![1], vec![], vec![2], vec![]])
+            .filter(|raw| !raw.is_empty())
+            .map(|raw| Reading { celsius: raw[0] });
+
+        assert_eq!(stream.poll_next(), Some(Reading { celsius: 1 }));
+        assert_eq!(stream.poll_next(), Some(Reading { celsius: 2 }));
+        assert_eq!(stream.poll_next(), None);
+    }
+}
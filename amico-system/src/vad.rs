@@ -0,0 +1,199 @@
+//! Voice-activity gating for audio streams
+
+use std::time::Duration;
+
+use crate::Stream;
+
+/// Voice-activity detection parameters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VadConfig {
+    /// RMS energy above which a frame counts as voice, on a 0.0..=1.0 scale
+    /// relative to full-scale 16-bit audio
+    pub threshold: f32,
+    /// Silence needed after voice to end an utterance
+    pub min_silence: Duration,
+    /// Duration of each frame in the source stream
+    pub frame_duration: Duration,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.02,
+            min_silence: Duration::from_millis(500),
+            frame_duration: Duration::from_millis(20),
+        }
+    }
+}
+
+/// A run of speech, bounded by silence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Utterance {
+    /// 16-bit PCM samples, including short pauses but not trailing silence
+    pub samples: Vec<i16>,
+    pub duration: Duration,
+}
+
+/// RMS energy of 16-bit samples, normalized to 0.0..=1.0
+pub fn rms_energy(samples: &[i16]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f64 = samples
+        .iter()
+        .map(|sample| {
+            let normalized = *sample as f64 / i16::MAX as f64;
+            normalized * normalized
+        })
+        .sum();
+    (sum / samples.len() as f64).sqrt() as f32
+}
+
+/// Stream adapter that turns audio frames into utterances.
+///
+/// Frames whose RMS energy is below the threshold are silence. Silence
+/// before speech is dropped; pauses shorter than `min_silence` are kept
+/// inside the utterance; once `min_silence` has elapsed the utterance is
+/// emitted without its trailing silence. This keeps continuous
+/// speech-to-text from transcribing (and paying for) silence.
+///
+/// Frames are any `AsRef<[i16]>` of 16-bit PCM samples.
+pub struct VadGate<S> {
+    inner: S,
+    config: VadConfig,
+    voiced: Vec<i16>,
+    voiced_duration: Duration,
+    silence: Vec<i16>,
+    silence_duration: Duration,
+}
+
+impl<S> VadGate<S> {
+    pub fn new(inner: S, config: VadConfig) -> Self {
+        Self {
+            inner,
+            config,
+            voiced: Vec::new(),
+            voiced_duration: Duration::ZERO,
+            silence: Vec::new(),
+            silence_duration: Duration::ZERO,
+        }
+    }
+
+    /// Emit the utterance in progress, if any, without waiting for silence.
+    ///
+    /// Call this when the source ends so trailing speech is not lost.
+    pub fn flush(&mut self) -> Option<Utterance> {
+        self.silence.clear();
+        self.silence_duration = Duration::ZERO;
+        if self.voiced.is_empty() {
+            return None;
+        }
+        Some(Utterance {
+            samples: std::mem::take(&mut self.voiced),
+            duration: std::mem::replace(&mut self.voiced_duration, Duration::ZERO),
+        })
+    }
+}
+
+impl<S> Stream for VadGate<S>
+where
+    S: Stream,
+    S::Item: AsRef<[i16]>,
+{
+    type Item = Utterance;
+
+    fn poll_next(&mut self) -> Option<Utterance> {
+        while let Some(frame) = self.inner.poll_next() {
+            let samples = frame.as_ref();
+
+            if rms_energy(samples) >= self.config.threshold {
+                // A pause shorter than `min_silence` belongs to the utterance
+                self.voiced.append(&mut self.silence);
+                self.voiced_duration += self.silence_duration + self.config.frame_duration;
+                self.silence_duration = Duration::ZERO;
+                self.voiced.extend_from_slice(samples);
+            } else if !self.voiced.is_empty() {
+                self.silence.extend_from_slice(samples);
+                self.silence_duration += self.config.frame_duration;
+                if self.silence_duration >= self.config.min_silence {
+                    return self.flush();
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    struct Frames(VecDeque<Vec<i16>>);
+
+    impl Stream for Frames {
+        type Item = Vec<i16>;
+
+        fn poll_next(&mut self) -> Option<Vec<i16>> {
+            self.0.pop_front()
+        }
+    }
+
+    fn loud() -> Vec<i16> {
+        vec![8000, -8000, 8000, -8000]
+    }
+
+    fn quiet() -> Vec<i16> {
+        vec![10, -10, 10, -10]
+    }
+
+    fn gate(frames: Vec<Vec<i16>>) -> VadGate<Frames> {
+        VadGate::new(
+            Frames(frames.into()),
+            VadConfig {
+                threshold: 0.1,
+                min_silence: Duration::from_millis(30),
+                frame_duration: Duration::from_millis(10),
+            },
+        )
+    }
+
+    #[test]
+    fn test_rms_energy() {
+        assert_eq!(rms_energy(&[]), 0.0);
+        assert!(rms_energy(&loud()) > 0.2);
+        assert!(rms_energy(&quiet()) < 0.01);
+    }
+
+    #[test]
+    fn test_vad_gate_groups_utterances() {
+        let mut vad = gate(vec![
+            quiet(),
+            loud(),
+            quiet(), // short pause, kept
+            loud(),
+            quiet(),
+            quiet(),
+            quiet(), // utterance ends here
+            quiet(),
+            loud(),
+        ]);
+
+        let first = vad.poll_next().unwrap();
+        assert_eq!(first.samples.len(), 12);
+        assert_eq!(first.duration, Duration::from_millis(30));
+
+        // The second utterance has not been closed by silence yet
+        assert_eq!(vad.poll_next(), None);
+        let second = vad.flush().unwrap();
+        assert_eq!(second.samples, loud());
+        assert_eq!(vad.flush(), None);
+    }
+
+    #[test]
+    fn test_vad_gate_drops_silence() {
+        let mut vad = gate(vec![quiet(); 10]);
+        assert_eq!(vad.poll_next(), None);
+        assert_eq!(vad.flush(), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! Output length limits for agent workflows
+
+use amico_runtime::Workflow;
+
+use crate::{AgentFinishReason, AgentResponse};
+
+/// Marker appended to content cut short by [`Bounded`]
+pub const TRUNCATION_MARKER: &str = "\n[output truncated]";
+
+/// Workflow wrapper that caps the length of an agent's final answer.
+///
+/// Content longer than `max_output_chars` characters is cut to that length,
+/// `TRUNCATION_MARKER` is appended and the finish reason is set to
+/// `AgentFinishReason::Truncated`. Intermediate steps are left untouched.
+pub struct Bounded<W> {
+    inner: W,
+    max_output_chars: usize,
+}
+
+impl<W> Bounded<W> {
+    pub fn new(inner: W, max_output_chars: usize) -> Self {
+        Self {
+            inner,
+            max_output_chars,
+        }
+    }
+
+    /// Get a reference to the wrapped workflow
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W> Workflow for Bounded<W>
+where
+    W: Workflow<Output = AgentResponse> + Sync,
+    W::Context: Sync,
+    W::Input: Send,
+{
+    type Context = W::Context;
+    type Input = W::Input;
+    type Output = AgentResponse;
+    type Error = W::Error;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<AgentResponse, Self::Error> {
+        let mut response = self.inner.execute(context, input).await?;
+
+        if let Some((cut, _)) = response.content.char_indices().nth(self.max_output_chars) {
+            response.content.truncate(cut);
+            response.content.push_str(TRUNCATION_MARKER);
+            response.finish_reason = AgentFinishReason::Truncated;
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkflowError;
+
+    /// Answers with its input
+    struct Echo;
+
+    impl Workflow for Echo {
+        type Context = ();
+        type Input = String;
+        type Output = AgentResponse;
+        type Error = WorkflowError;
+
+        async fn execute<'a>(
+            &'a self,
+            _context: &'a (),
+            input: String,
+        ) -> Result<AgentResponse, WorkflowError> {
+            Ok(AgentResponse {
+                content: input,
+                steps: vec![],
+                finish_reason: AgentFinishReason::Success,
+                iterations: 1,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bounded_truncates_oversized_response() {
+        let workflow = Bounded::new(Echo, 5);
+
+        let response = workflow
+            .execute(&(), "héllo world".to_string())
+            .await
+            .unwrap();
+        assert_eq!(response.content, format!("héllo{}", TRUNCATION_MARKER));
+        assert_eq!(response.finish_reason, AgentFinishReason::Truncated);
+
+        let response = workflow.execute(&(), "hello".to_string()).await.unwrap();
+        assert_eq!(response.content, "hello");
+        assert_eq!(response.finish_reason, AgentFinishReason::Success);
+    }
+}
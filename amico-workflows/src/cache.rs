@@ -0,0 +1,264 @@
+//! Input-keyed result caching for deterministic workflows
+
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use amico_runtime::{Monotonic, Workflow};
+
+struct Entry<V> {
+    value: V,
+    inserted: Monotonic,
+    last_used: u64,
+}
+
+/// Least-recently-used map with deterministic eviction.
+///
+/// Every access takes the next value of a logical clock, so the entry
+/// evicted when the cache is full depends only on the order of accesses.
+struct Lru<K, V> {
+    entries: HashMap<K, Entry<V>>,
+    /// Entries ordered by their last access
+    order: BTreeMap<u64, K>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> Lru<K, V> {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.last_used);
+        }
+    }
+
+    fn get(&mut self, key: &K, ttl: Option<Duration>) -> Option<V> {
+        let expired = {
+            let entry = self.entries.get(key)?;
+            ttl.is_some_and(|ttl| entry.inserted.elapsed() >= ttl)
+        };
+        if expired {
+            self.remove(key);
+            return None;
+        }
+
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        self.order.remove(&entry.last_used);
+        entry.last_used = tick;
+        self.order.insert(tick, key.clone());
+        Some(entry.value.clone())
+    }
+
+    fn insert(&mut self, key: K, value: V, capacity: usize) {
+        self.remove(&key);
+        while self.entries.len() >= capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+
+        let tick = self.next_tick();
+        self.order.insert(tick, key.clone());
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                inserted: Monotonic::start(),
+                last_used: tick,
+            },
+        );
+    }
+}
+
+/// Workflow wrapper that caches successful outputs by input.
+///
+/// A cache hit returns the stored output without calling the inner
+/// workflow. Errors are never cached. When full, the least recently used
+/// entry is evicted; with a TTL, entries older than the TTL are treated as
+/// misses. Only wrap workflows whose output depends on the input alone.
+///
+/// ```rust,ignore
+/// let embeddings = Cached::new(embed_workflow, 1024).with_ttl(Duration::from_secs(3600));
+/// ```
+pub struct Cached<W: Workflow> {
+    inner: W,
+    capacity: usize,
+    ttl: Option<Duration>,
+    cache: Mutex<Lru<W::Input, W::Output>>,
+}
+
+impl<W> Cached<W>
+where
+    W: Workflow,
+    W::Input: Hash + Eq + Clone,
+    W::Output: Clone,
+{
+    /// Wrap `inner` with a cache holding up to `capacity` outputs
+    pub fn new(inner: W, capacity: usize) -> Self {
+        Self {
+            inner,
+            capacity,
+            ttl: None,
+            cache: Mutex::new(Lru::new()),
+        }
+    }
+
+    /// Expire entries `ttl` after they were stored
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Number of cached outputs, including expired ones not yet evicted
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached outputs
+    pub fn clear(&self) {
+        *self.cache.lock().unwrap() = Lru::new();
+    }
+
+    /// Get a reference to the wrapped workflow
+    pub fn inner(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W> Workflow for Cached<W>
+where
+    W: Workflow + Sync,
+    W::Context: Sync,
+    W::Input: Hash + Eq + Clone + Send,
+    W::Output: Clone + Send,
+{
+    type Context = W::Context;
+    type Input = W::Input;
+    type Output = W::Output;
+    type Error = W::Error;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        if self.capacity == 0 {
+            return self.inner.execute(context, input).await;
+        }
+        if let Some(output) = self.cache.lock().unwrap().get(&input, self.ttl) {
+            return Ok(output);
+        }
+
+        let output = self.inner.execute(context, input.clone()).await?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(input, output.clone(), self.capacity);
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WorkflowError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Returns the length of its input, counting executions
+    #[derive(Default)]
+    struct Length {
+        calls: AtomicUsize,
+    }
+
+    impl Workflow for Length {
+        type Context = ();
+        type Input = String;
+        type Output = usize;
+        type Error = WorkflowError;
+
+        async fn execute<'a>(
+            &'a self,
+            _context: &'a (),
+            input: String,
+        ) -> Result<usize, Self::Error> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if input.is_empty() {
+                return Err(WorkflowError::Other("empty input".to_string()));
+            }
+            Ok(input.len())
+        }
+    }
+
+    fn calls(cached: &Cached<Length>) -> usize {
+        cached.inner().calls.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_and_miss() {
+        let cached = Cached::new(Length::default(), 8);
+
+        assert_eq!(cached.execute(&(), "abc".to_string()).await.unwrap(), 3);
+        assert_eq!(cached.execute(&(), "abc".to_string()).await.unwrap(), 3);
+        assert_eq!(calls(&cached), 1);
+
+        assert_eq!(cached.execute(&(), "hello".to_string()).await.unwrap(), 5);
+        assert_eq!(calls(&cached), 2);
+
+        // Errors are not cached
+        assert!(cached.execute(&(), String::new()).await.is_err());
+        assert!(cached.execute(&(), String::new()).await.is_err());
+        assert_eq!(calls(&cached), 4);
+        assert_eq!(cached.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_evicts_least_recently_used() {
+        let cached = Cached::new(Length::default(), 2);
+
+        for input in ["a", "bb", "a", "ccc"] {
+            cached.execute(&(), input.to_string()).await.unwrap();
+        }
+        assert_eq!(calls(&cached), 3);
+
+        // "bb" was least recently used when "ccc" was inserted
+        cached.execute(&(), "a".to_string()).await.unwrap();
+        assert_eq!(calls(&cached), 3);
+        cached.execute(&(), "bb".to_string()).await.unwrap();
+        assert_eq!(calls(&cached), 4);
+    }
+
+    #[tokio::test]
+    async fn test_cache_ttl_expiry() {
+        // The TTL leaves ample margin for the first two calls on a loaded
+        // machine; expiry only needs the sleep to last at least the TTL
+        let ttl = Duration::from_millis(500);
+        let cached = Cached::new(Length::default(), 8).with_ttl(ttl);
+
+        cached.execute(&(), "abc".to_string()).await.unwrap();
+        cached.execute(&(), "abc".to_string()).await.unwrap();
+        assert_eq!(calls(&cached), 1);
+
+        tokio::time::sleep(ttl).await;
+        cached.execute(&(), "abc".to_string()).await.unwrap();
+        assert_eq!(calls(&cached), 2);
+    }
+}
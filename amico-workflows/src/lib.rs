@@ -24,32 +24,103 @@
 //! let result = agent.execute(&context, "What is 2+2?".to_string()).await?;
 //! ```
 
-use amico_runtime::{Workflow, ExecutionContext};
+use amico_models::{LanguageInput, LanguageModel, ToolCall};
+use amico_runtime::{ControlContext, Workflow, ExecutionContext};
+use amico_system::Tool;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::future::Future;
 
+mod bounded;
+pub use bounded::{Bounded, TRUNCATION_MARKER};
+
+mod cache;
+pub use cache::Cached;
+
+mod metered;
+pub use metered::{MeteredModel, MeteredTool};
+
+mod observer;
+pub use observer::AgentObserver;
+
+mod tokens;
+pub use tokens::{count_messages, truncate_messages, CharEstimate, Message, Role, TokenCounter};
+
 /// Agent response
 #[derive(Debug, Clone)]
 pub struct AgentResponse {
     pub content: String,
     pub steps: Vec<AgentStep>,
     pub finish_reason: AgentFinishReason,
+    /// Number of model calls the agent made
+    pub iterations: usize,
 }
 
 /// Individual step in agent reasoning
+///
+/// `action` and `observation` are for display; `tool_call` and
+/// `tool_result` carry the same information in machine-readable form.
 #[derive(Debug, Clone)]
 pub struct AgentStep {
     pub thought: String,
     pub action: Option<String>,
     pub observation: Option<String>,
+    /// The tool call made in this step
+    pub tool_call: Option<ToolCall>,
+    /// The tool's output, parsed as JSON when possible and kept as a JSON
+    /// string otherwise; `None` if the call failed
+    pub tool_result: Option<serde_json::Value>,
+}
+
+impl AgentStep {
+    /// A step with only a thought
+    pub fn thought(thought: impl Into<String>) -> Self {
+        Self {
+            thought: thought.into(),
+            action: None,
+            observation: None,
+            tool_call: None,
+            tool_result: None,
+        }
+    }
+
+    /// Attach a tool call and its result, filling in the display fields
+    pub fn with_tool_call(mut self, call: ToolCall, result: Result<String, String>) -> Self {
+        self.action = Some(format!("{}({})", call.name, call.arguments));
+        self.tool_call = Some(call);
+        match result {
+            Ok(output) => {
+                self.tool_result = Some(
+                    serde_json::from_str(&output)
+                        .unwrap_or_else(|_| serde_json::Value::String(output.clone())),
+                );
+                self.observation = Some(output);
+            }
+            Err(err) => {
+                self.tool_result = None;
+                self.observation = Some(format!("Error: {}", err));
+            }
+        }
+        self
+    }
 }
 
 /// Reason why agent finished
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AgentFinishReason {
+    /// The model produced a final answer
     Success,
+    /// The iteration cap was reached before a final answer
     MaxIterations,
+    /// Tool calls failed too many times in a row
+    ToolFailures,
     Error,
+    /// The final answer was cut to fit an output limit
+    Truncated,
+    /// The context's cancellation token was triggered
+    Cancelled,
+    /// The context's deadline passed before a final answer
+    DeadlineExceeded,
 }
 
 /// Workflow error
@@ -83,6 +154,47 @@ pub trait ToolRegistry {
     fn list_tools(&self) -> Vec<&Self::ToolName>;
 }
 
+impl<T> ToolRegistry for HashMap<String, T> {
+    type Tool = T;
+    type ToolName = String;
+
+    fn get_tool(&self, name: &String) -> Option<&T> {
+        self.get(name)
+    }
+
+    fn list_tools(&self) -> Vec<&String> {
+        self.keys().collect()
+    }
+}
+
+/// Description of a configured agent, for admin and debug endpoints
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentInfo {
+    /// Workflow pattern, e.g. `"tool_loop"`
+    pub workflow: &'static str,
+    /// Type name of the model (models carry no runtime name)
+    pub model: &'static str,
+    /// Registered tool names, sorted
+    pub tools: Vec<String>,
+    pub max_iterations: usize,
+}
+
+impl AgentInfo {
+    fn new<M, T>(workflow: &'static str, tools: &T, max_iterations: usize) -> Self
+    where
+        T: ToolRegistry<ToolName = String>,
+    {
+        let mut tools: Vec<String> = tools.list_tools().into_iter().cloned().collect();
+        tools.sort();
+        Self {
+            workflow,
+            model: std::any::type_name::<M>(),
+            tools,
+            max_iterations,
+        }
+    }
+}
+
 /// Tool loop agent - repeatedly calls tools until goal is met
 ///
 /// This workflow:
@@ -91,11 +203,24 @@ pub trait ToolRegistry {
 /// 3. Executes tool if needed
 /// 4. Observes result
 /// 5. Repeats until task is complete or max iterations reached
-pub struct ToolLoopAgent<M, T, C> {
+///
+/// Tools take the model's raw argument string and return a string
+/// observation, which is appended to the prompt for the next iteration.
+/// Tool failures and unknown tools are reported back to the model as
+/// observations so it can recover; after `max_tool_failures` consecutive
+/// failures (3 by default) the loop gives up with
+/// `AgentFinishReason::ToolFailures`.
+///
+/// Before each model call the agent checks the context's `ControlContext`
+/// and stops with `AgentFinishReason::Cancelled` or
+/// `AgentFinishReason::DeadlineExceeded`, keeping the steps taken so far.
+pub struct ToolLoopAgent<M, T, C, O = ()> {
     model: M,
     tools: T,
     max_iterations: usize,
-    _context: PhantomData<C>,
+    max_tool_failures: usize,
+    observer: O,
+    _context: PhantomData<fn(&C)>,
 }
 
 impl<M, T, C> ToolLoopAgent<M, T, C> {
@@ -104,39 +229,136 @@ impl<M, T, C> ToolLoopAgent<M, T, C> {
             model,
             tools,
             max_iterations,
+            max_tool_failures: 3,
+            observer: (),
             _context: PhantomData,
         }
     }
 }
 
-impl<M, T, C> Workflow for ToolLoopAgent<M, T, C>
+impl<M, T, C, O> ToolLoopAgent<M, T, C, O> {
+    /// Stop after this many consecutive failed tool calls; 0 behaves like 1
+    /// and stops at the first failure
+    pub fn with_max_tool_failures(mut self, max_tool_failures: usize) -> Self {
+        self.max_tool_failures = max_tool_failures;
+        self
+    }
+
+    /// Notify `observer` after each tool call
+    pub fn with_observer<O2: AgentObserver>(self, observer: O2) -> ToolLoopAgent<M, T, C, O2> {
+        ToolLoopAgent {
+            model: self.model,
+            tools: self.tools,
+            max_iterations: self.max_iterations,
+            max_tool_failures: self.max_tool_failures,
+            observer,
+            _context: PhantomData,
+        }
+    }
+
+    /// Describe the agent's configuration
+    pub fn info(&self) -> AgentInfo
+    where
+        T: ToolRegistry<ToolName = String>,
+    {
+        AgentInfo::new::<M, T>("tool_loop", &self.tools, self.max_iterations)
+    }
+}
+
+impl<M, T, C, O> Workflow for ToolLoopAgent<M, T, C, O>
 where
-    M: Send + Sync,
-    T: Send + Sync,
-    C: ExecutionContext + Send + Sync,
+    M: LanguageModel<Context = C> + Sync,
+    M::Error: std::fmt::Display,
+    T: ToolRegistry<ToolName = String> + Sync,
+    T::Tool: Tool<Input = String, Output = String> + Sync,
+    <T::Tool as Tool>::Error: std::fmt::Display,
+    C: ExecutionContext + ControlContext + Sync,
+    O: AgentObserver + Sync,
 {
     type Context = C;
     type Input = String;
     type Output = AgentResponse;
     type Error = WorkflowError;
-    
+
     async fn execute<'a>(
         &'a self,
-        _context: &'a Self::Context,
+        context: &'a Self::Context,
         input: Self::Input,
     ) -> Result<Self::Output, Self::Error> {
-        // Placeholder implementation
-        // In a real implementation, this would:
-        // 1. Loop up to max_iterations
-        // 2. Call model to decide action
-        // 3. Execute tool if needed
-        // 4. Collect observations
-        // 5. Return when goal is met
-        
+        let mut prompt = input;
+        let mut steps = Vec::new();
+        let mut consecutive_failures = 0;
+
+        for iteration in 1..=self.max_iterations {
+            let stopped = if context.is_cancelled() {
+                Some(AgentFinishReason::Cancelled)
+            } else if context.is_expired() {
+                Some(AgentFinishReason::DeadlineExceeded)
+            } else {
+                None
+            };
+            if let Some(finish_reason) = stopped {
+                return Ok(AgentResponse {
+                    content: String::new(),
+                    steps,
+                    finish_reason,
+                    iterations: iteration - 1,
+                });
+            }
+
+            let output = self
+                .model
+                .execute(context, LanguageInput::new(prompt.clone()))
+                .await
+                .map_err(|err| WorkflowError::ModelError(err.to_string()))?;
+
+            if output.tool_calls.is_empty() {
+                return Ok(AgentResponse {
+                    content: output.text,
+                    steps,
+                    finish_reason: AgentFinishReason::Success,
+                    iterations: iteration,
+                });
+            }
+
+            for call in output.tool_calls {
+                let result = match self.tools.get_tool(&call.name) {
+                    Some(tool) => tool
+                        .execute(call.arguments.clone())
+                        .await
+                        .map_err(|err| err.to_string()),
+                    None => Err(format!("unknown tool {}", call.name)),
+                };
+                let failed = result.is_err();
+                if failed {
+                    consecutive_failures += 1;
+                } else {
+                    consecutive_failures = 0;
+                }
+
+                let step = AgentStep::thought(output.text.clone()).with_tool_call(call, result);
+                if let (Some(action), Some(observation)) = (&step.action, &step.observation) {
+                    prompt.push_str(&format!("\n\nTool {} returned: {}", action, observation));
+                }
+                self.observer.on_step(&step).await;
+                steps.push(step);
+
+                if failed && consecutive_failures >= self.max_tool_failures {
+                    return Ok(AgentResponse {
+                        content: String::new(),
+                        steps,
+                        finish_reason: AgentFinishReason::ToolFailures,
+                        iterations: iteration,
+                    });
+                }
+            }
+        }
+
         Ok(AgentResponse {
-            content: format!("Response to: {}", input),
-            steps: vec![],
-            finish_reason: AgentFinishReason::Success,
+            content: String::new(),
+            steps,
+            finish_reason: AgentFinishReason::MaxIterations,
+            iterations: self.max_iterations,
         })
     }
 }
@@ -154,6 +376,7 @@ pub struct ThoughtStep {
 /// 1. Decompose problem into sub-problems
 /// 2. Solve each sub-problem sequentially
 /// 3. Combine results
+#[allow(dead_code)] // Fields are consumed once the placeholder loop is implemented
 pub struct ChainOfThought<M> {
     model: M,
     steps: Vec<ThoughtStep>,
@@ -184,6 +407,7 @@ where
             content: format!("Chain of thought response to: {}", input),
             steps: vec![],
             finish_reason: AgentFinishReason::Success,
+            iterations: 0,
         })
     }
 }
@@ -196,6 +420,7 @@ where
 /// 3. Execute the action
 /// 4. Observe the result
 /// 5. Repeat
+#[allow(dead_code)] // Fields are consumed once the placeholder loop is implemented
 pub struct ReActWorkflow<M, T> {
     model: M,
     tools: T,
@@ -210,6 +435,14 @@ impl<M, T> ReActWorkflow<M, T> {
             max_iterations,
         }
     }
+
+    /// Describe the workflow's configuration
+    pub fn info(&self) -> AgentInfo
+    where
+        T: ToolRegistry<ToolName = String>,
+    {
+        AgentInfo::new::<M, T>("react", &self.tools, self.max_iterations)
+    }
 }
 
 impl<M, T> Workflow for ReActWorkflow<M, T>
@@ -232,6 +465,7 @@ where
             content: format!("ReAct response to: {}", input),
             steps: vec![],
             finish_reason: AgentFinishReason::Success,
+            iterations: 0,
         })
     }
 }
@@ -243,6 +477,7 @@ where
 /// 2. Critique the response
 /// 3. Refine based on critique
 /// 4. Repeat until satisfactory
+#[allow(dead_code)] // Fields are consumed once the placeholder loop is implemented
 pub struct ReflectionWorkflow<M> {
     model: M,
     critic: M,
@@ -278,6 +513,7 @@ where
             content: format!("Reflection response to: {}", input),
             steps: vec![],
             finish_reason: AgentFinishReason::Success,
+            iterations: 0,
         })
     }
 }
@@ -306,3 +542,208 @@ pub trait MultiAgentWorkflow {
         responses: Vec<AgentResponse>,
     ) -> impl Future<Output = Self::Coordination> + Send + 'a;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amico_models::ScriptedModel;
+    use amico_runtime::{CancellationToken, SimpleContext};
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    type TestContext = SimpleContext<(), ()>;
+
+    /// Uppercases its input; fails on empty input
+    struct Upper;
+
+    impl Tool for Upper {
+        type Input = String;
+        type Output = String;
+        type Error = WorkflowError;
+
+        async fn execute(&self, input: String) -> Result<String, WorkflowError> {
+            if input.is_empty() {
+                return Err(WorkflowError::ToolError("nothing to uppercase".to_string()));
+            }
+            Ok(input.to_uppercase())
+        }
+
+        fn name(&self) -> &str {
+            "upper"
+        }
+
+        fn description(&self) -> &str {
+            "Uppercases text"
+        }
+    }
+
+    fn tools() -> HashMap<String, Upper> {
+        HashMap::from([("upper".to_string(), Upper)])
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_agent_observer_sees_steps_in_order() {
+        let model = ScriptedModel::<TestContext>::new()
+            .then_tool_call("upper", "hello")
+            .then_tool_call("missing", "x")
+            .then_text("HELLO it is");
+        let seen = Mutex::new(Vec::new());
+        let agent = ToolLoopAgent::new(model, tools(), 5).with_observer(|step: &AgentStep| {
+            seen.lock().unwrap().push(step.clone());
+        });
+
+        let response = agent
+            .execute(&SimpleContext::new((), ()), "Shout hello".to_string())
+            .await
+            .unwrap();
+        assert_eq!(response.content, "HELLO it is");
+        assert_eq!(response.finish_reason, AgentFinishReason::Success);
+        assert_eq!(response.iterations, 3);
+
+        let seen = seen.into_inner().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].action.as_deref(), Some("upper(hello)"));
+        assert_eq!(seen[0].observation.as_deref(), Some("HELLO"));
+        assert_eq!(seen[0].tool_call.as_ref().unwrap().name, "upper");
+        assert_eq!(seen[0].tool_result, Some(serde_json::json!("HELLO")));
+        assert_eq!(seen[1].action.as_deref(), Some("missing(x)"));
+        assert_eq!(
+            seen[1].observation.as_deref(),
+            Some("Error: unknown tool missing")
+        );
+        assert_eq!(response.steps.len(), 2);
+    }
+
+    #[test]
+    fn test_agent_info() {
+        let mut tools = tools();
+        tools.insert("echo".to_string(), Upper);
+
+        let agent: ToolLoopAgent<_, _, TestContext> =
+            ToolLoopAgent::new(ScriptedModel::<TestContext>::new(), tools, 7);
+        let info = agent.info();
+        assert_eq!(info.workflow, "tool_loop");
+        assert_eq!(info.tools, ["echo", "upper"]);
+        assert_eq!(info.max_iterations, 7);
+        assert!(info.model.contains("ScriptedModel"));
+
+        let react = ReActWorkflow::new(ScriptedModel::<()>::new(), self::tools(), 4);
+        let info = react.info();
+        assert_eq!(info.workflow, "react");
+        assert_eq!(info.tools, ["upper"]);
+        assert_eq!(info.max_iterations, 4);
+    }
+
+    #[test]
+    fn test_agent_step_structured_tool_data() {
+        let call = ToolCall {
+            id: "call_1".to_string(),
+            name: "weather".to_string(),
+            arguments: r#"{"city":"Paris"}"#.to_string(),
+        };
+
+        let step = AgentStep::thought("Check the weather")
+            .with_tool_call(call.clone(), Ok(r#"{"temp_c":21}"#.to_string()));
+        assert_eq!(step.action.as_deref(), Some(r#"weather({"city":"Paris"})"#));
+        assert_eq!(step.tool_call, Some(call.clone()));
+        assert_eq!(step.tool_result, Some(serde_json::json!({ "temp_c": 21 })));
+
+        let step = AgentStep::thought("").with_tool_call(call.clone(), Ok("sunny".to_string()));
+        assert_eq!(step.tool_result, Some(serde_json::json!("sunny")));
+
+        let step = AgentStep::thought("").with_tool_call(call, Err("timeout".to_string()));
+        assert_eq!(step.observation.as_deref(), Some("Error: timeout"));
+        assert_eq!(step.tool_result, None);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_agent_zero_failure_limit() {
+        let model = ScriptedModel::<TestContext>::new()
+            .then_tool_call("upper", "ok")
+            .then_text("done")
+            .then_tool_call("upper", "")
+            .then_text("never reached");
+        let agent = ToolLoopAgent::new(model, tools(), 5).with_max_tool_failures(0);
+        let context = SimpleContext::new((), ());
+
+        // Successful tool calls never count against the limit
+        let response = agent.execute(&context, "go".to_string()).await.unwrap();
+        assert_eq!(response.finish_reason, AgentFinishReason::Success);
+        assert_eq!(response.content, "done");
+
+        // With a limit of zero the first failure ends the run
+        let response = agent.execute(&context, "go".to_string()).await.unwrap();
+        assert_eq!(response.finish_reason, AgentFinishReason::ToolFailures);
+        assert_eq!(response.iterations, 1);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_agent_stops_when_cancelled() {
+        let model = ScriptedModel::<TestContext>::new()
+            .then_tool_call("upper", "a")
+            .then_tool_call("upper", "b")
+            .then_text("never reached");
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        // Cancel as soon as the first tool call has been observed
+        let agent = ToolLoopAgent::new(model, tools(), 5)
+            .with_observer(move |_: &AgentStep| canceller.cancel());
+        let context = SimpleContext::new((), ()).with_cancellation(token);
+
+        let response = agent.execute(&context, "go".to_string()).await.unwrap();
+        assert_eq!(response.finish_reason, AgentFinishReason::Cancelled);
+        assert_eq!(response.iterations, 1);
+        assert_eq!(response.steps.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_agent_stops_past_deadline() {
+        let model = ScriptedModel::<TestContext>::new().then_text("too late");
+        let agent = ToolLoopAgent::new(model, tools(), 5);
+        let context = SimpleContext::new((), ()).with_deadline(Instant::now());
+
+        let response = agent.execute(&context, "go".to_string()).await.unwrap();
+        assert_eq!(response.finish_reason, AgentFinishReason::DeadlineExceeded);
+        assert_eq!(response.iterations, 0);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_agent_max_iterations() {
+        let model = ScriptedModel::<TestContext>::new()
+            .then_tool_call("upper", "a")
+            .then_tool_call("upper", "b")
+            .then_text("done");
+        let agent = ToolLoopAgent::new(model, tools(), 2);
+
+        let response = agent
+            .execute(&SimpleContext::new((), ()), "go".to_string())
+            .await
+            .unwrap();
+        assert_eq!(response.finish_reason, AgentFinishReason::MaxIterations);
+        assert_eq!(response.iterations, 2);
+        assert_eq!(response.steps.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_agent_repeated_tool_failures() {
+        let model = ScriptedModel::<TestContext>::new()
+            .then_tool_call("upper", "")
+            .then_tool_call("upper", "ok")
+            .then_tool_call("upper", "")
+            .then_tool_call("missing", "")
+            .then_text("never reached");
+        let agent = ToolLoopAgent::new(model, tools(), 10).with_max_tool_failures(2);
+
+        let response = agent
+            .execute(&SimpleContext::new((), ()), "go".to_string())
+            .await
+            .unwrap();
+        // The success in between resets the failure count
+        assert_eq!(response.finish_reason, AgentFinishReason::ToolFailures);
+        assert_eq!(response.iterations, 4);
+        assert_eq!(
+            response.steps[3].observation.as_deref(),
+            Some("Error: unknown tool missing")
+        );
+    }
+}
@@ -0,0 +1,342 @@
+//! Metrics wrappers for models and tools
+
+use std::time::Duration;
+
+use amico_models::{LanguageInput, LanguageModel, LanguageOutput, Model};
+use amico_runtime::{Metrics, Monotonic, SpanContext, SpanRecord, TracedContext};
+use amico_system::Tool;
+
+fn status<T, E>(result: &Result<T, E>) -> &'static str {
+    if result.is_ok() {
+        "ok"
+    } else {
+        "error"
+    }
+}
+
+/// Report a child span of `parent` covering an operation that just finished
+fn record_child_span<R: Metrics>(
+    metrics: &R,
+    parent: Option<&SpanContext>,
+    name: String,
+    duration: Duration,
+    ok: bool,
+) {
+    if let Some(parent) = parent {
+        metrics.record_span(&SpanRecord {
+            name,
+            context: parent.child(),
+            duration,
+            ok,
+        });
+    }
+}
+
+/// Language model wrapper that records request metrics.
+///
+/// Every call records:
+/// - `model_requests_total{model, status}`, where status is `ok` or `error`
+/// - `model_latency_ms{model}`
+/// - `model_tokens_total{model, kind}` for `prompt` and `completion` tokens
+///
+/// When the context carries a tracing span, a `model <name>` child span is
+/// also reported via `Metrics::record_span`.
+pub struct MeteredModel<M, R> {
+    inner: M,
+    name: String,
+    metrics: R,
+}
+
+impl<M, R> MeteredModel<M, R> {
+    /// Wrap `inner`, labelling its metrics with `name`
+    pub fn new(inner: M, name: impl Into<String>, metrics: R) -> Self {
+        Self {
+            inner,
+            name: name.into(),
+            metrics,
+        }
+    }
+
+    /// Get a reference to the wrapped model
+    pub fn inner(&self) -> &M {
+        &self.inner
+    }
+}
+
+impl<M, R> Model for MeteredModel<M, R>
+where
+    M: LanguageModel + Sync,
+    M::Context: TracedContext + Sync,
+    R: Metrics + Sync,
+{
+    type Context = M::Context;
+    type Input = LanguageInput;
+    type Output = LanguageOutput;
+    type Error = M::Error;
+
+    async fn execute<'a>(
+        &'a self,
+        context: &'a Self::Context,
+        input: Self::Input,
+    ) -> Result<Self::Output, Self::Error> {
+        let clock = Monotonic::start();
+        let result = self.inner.execute(context, input).await;
+        let elapsed = clock.elapsed();
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+
+        let model = self.name.as_str();
+        self.metrics.increment_counter(
+            "model_requests_total",
+            &[("model", model), ("status", status(&result))],
+            1,
+        );
+        self.metrics
+            .record_histogram("model_latency_ms", &[("model", model)], elapsed_ms);
+        if let Ok(output) = &result {
+            self.metrics.increment_counter(
+                "model_tokens_total",
+                &[("model", model), ("kind", "prompt")],
+                output.usage.prompt_tokens as u64,
+            );
+            self.metrics.increment_counter(
+                "model_tokens_total",
+                &[("model", model), ("kind", "completion")],
+                output.usage.completion_tokens as u64,
+            );
+        }
+        record_child_span(
+            &self.metrics,
+            context.span(),
+            format!("model {}", model),
+            elapsed,
+            result.is_ok(),
+        );
+        result
+    }
+}
+
+impl<M, R> LanguageModel for MeteredModel<M, R>
+where
+    M: LanguageModel + Sync,
+    M::Context: TracedContext + Sync,
+    R: Metrics + Sync,
+{
+}
+
+/// Tool wrapper that records execution metrics.
+///
+/// Every call records `tool_requests_total{tool, status}` and
+/// `tool_latency_ms{tool}`, labelled with the tool's own name. Tools do
+/// not receive an execution context, so use [`MeteredTool::execute_traced`]
+/// to also report a `tool <name>` child span.
+pub struct MeteredTool<T, R> {
+    inner: T,
+    metrics: R,
+}
+
+impl<T, R> MeteredTool<T, R> {
+    pub fn new(inner: T, metrics: R) -> Self {
+        Self { inner, metrics }
+    }
+
+    /// Get a reference to the wrapped tool
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T, R> MeteredTool<T, R>
+where
+    T: Tool + Sync,
+    R: Metrics,
+{
+    async fn execute_metered(
+        &self,
+        input: T::Input,
+        parent: Option<&SpanContext>,
+    ) -> Result<T::Output, T::Error> {
+        let clock = Monotonic::start();
+        let result = self.inner.execute(input).await;
+        let elapsed = clock.elapsed();
+
+        let tool = self.inner.name();
+        self.metrics.increment_counter(
+            "tool_requests_total",
+            &[("tool", tool), ("status", status(&result))],
+            1,
+        );
+        self.metrics.record_histogram(
+            "tool_latency_ms",
+            &[("tool", tool)],
+            elapsed.as_secs_f64() * 1000.0,
+        );
+        record_child_span(
+            &self.metrics,
+            parent,
+            format!("tool {}", tool),
+            elapsed,
+            result.is_ok(),
+        );
+        result
+    }
+
+    /// Execute the tool, reporting a child of the context's span
+    pub async fn execute_traced<C: TracedContext>(
+        &self,
+        context: &C,
+        input: T::Input,
+    ) -> Result<T::Output, T::Error> {
+        self.execute_metered(input, context.span()).await
+    }
+}
+
+impl<T, R> Tool for MeteredTool<T, R>
+where
+    T: Tool + Sync,
+    T::Input: Send,
+    R: Metrics + Sync,
+{
+    type Input = T::Input;
+    type Output = T::Output;
+    type Error = T::Error;
+
+    async fn execute(&self, input: Self::Input) -> Result<Self::Output, Self::Error> {
+        self.execute_metered(input, None).await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn input_schema(&self) -> Option<&str> {
+        self.inner.input_schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amico_models::{EchoModel, ScriptedModel};
+    use amico_runtime::{InMemoryMetrics, SimpleContext};
+
+    /// Parses its input as an integer and doubles it
+    struct Doubler;
+
+    impl Tool for Doubler {
+        type Input = String;
+        type Output = i64;
+        type Error = std::num::ParseIntError;
+
+        async fn execute(&self, input: String) -> Result<i64, Self::Error> {
+            Ok(input.trim().parse::<i64>()? * 2)
+        }
+
+        fn name(&self) -> &str {
+            "doubler"
+        }
+
+        fn description(&self) -> &str {
+            "Doubles an integer"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metered_model() {
+        let metrics = InMemoryMetrics::new();
+        let echo = MeteredModel::new(EchoModel::new(), "echo", &metrics);
+        let scripted = MeteredModel::new(ScriptedModel::new(), "scripted", &metrics);
+
+        echo.execute(&(), LanguageInput::new("hello world"))
+            .await
+            .unwrap();
+        scripted
+            .execute(&(), LanguageInput::new("hello"))
+            .await
+            .unwrap_err();
+
+        let snapshot = metrics.snapshot();
+        let requests = |model, status| {
+            snapshot.counter(
+                "model_requests_total",
+                &[("model", model), ("status", status)],
+            )
+        };
+        assert_eq!(requests("echo", "ok"), 1);
+        assert_eq!(requests("echo", "error"), 0);
+        assert_eq!(requests("scripted", "error"), 1);
+        assert_eq!(
+            snapshot.counter(
+                "model_tokens_total",
+                &[("model", "echo"), ("kind", "completion")]
+            ),
+            2
+        );
+        assert_eq!(
+            snapshot
+                .histogram("model_latency_ms", &[("model", "scripted")])
+                .unwrap()
+                .count,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_metered_tool() {
+        let metrics = InMemoryMetrics::new();
+        let tool = MeteredTool::new(Doubler, &metrics);
+
+        assert_eq!(tool.execute("21".to_string()).await.unwrap(), 42);
+        assert!(tool.execute("abc".to_string()).await.is_err());
+        assert_eq!(tool.name(), "doubler");
+
+        let snapshot = metrics.snapshot();
+        for status in ["ok", "error"] {
+            assert_eq!(
+                snapshot.counter(
+                    "tool_requests_total",
+                    &[("tool", "doubler"), ("status", status)]
+                ),
+                1
+            );
+        }
+        assert_eq!(
+            snapshot
+                .histogram("tool_latency_ms", &[("tool", "doubler")])
+                .unwrap()
+                .count,
+            2
+        );
+        assert!(snapshot.spans.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_metered_wrappers_emit_child_spans() {
+        let metrics = InMemoryMetrics::new();
+        let root = SpanContext::new_root();
+        let context = SimpleContext::new((), ()).with_span(root);
+
+        let model = MeteredModel::new(EchoModel::new(), "echo", &metrics);
+        model
+            .execute(&context, LanguageInput::new("hi"))
+            .await
+            .unwrap();
+        let tool = MeteredTool::new(Doubler, &metrics);
+        tool.execute_traced(&context, "nope".to_string())
+            .await
+            .unwrap_err();
+
+        let spans = metrics.snapshot().spans;
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0].name, "model echo");
+        assert_eq!(spans[1].name, "tool doubler");
+        assert!(!spans[1].ok);
+        for span in &spans {
+            assert_eq!(span.context.trace_id, root.trace_id);
+            assert_eq!(span.context.parent_span_id, Some(root.span_id));
+        }
+    }
+}
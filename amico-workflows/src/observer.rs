@@ -0,0 +1,31 @@
+//! Step observers for agent workflows
+
+use std::future::Future;
+
+use crate::AgentStep;
+
+/// Receives each tool step of an agent loop as it completes.
+///
+/// Lighter than full tracing: use it to log progress or stream steps to a
+/// UI. The default implementation does nothing, and `()` is the no-op
+/// observer. Plain closures taking `&AgentStep` are observers too.
+pub trait AgentObserver {
+    /// Called after each tool call with the step it produced.
+    ///
+    /// An iteration that makes several tool calls produces several steps;
+    /// the final answer, which calls no tool, produces none.
+    fn on_step<'a>(&'a self, _step: &'a AgentStep) -> impl Future<Output = ()> + Send + 'a {
+        async {}
+    }
+}
+
+impl AgentObserver for () {}
+
+impl<F> AgentObserver for F
+where
+    F: Fn(&AgentStep) + Sync,
+{
+    async fn on_step(&self, step: &AgentStep) {
+        self(step)
+    }
+}
@@ -0,0 +1,161 @@
+//! Approximate token counting and context-window truncation
+
+/// Counts tokens in a piece of text.
+///
+/// The default [`CharEstimate`] is a provider-agnostic heuristic; supply a
+/// provider-accurate tokenizer (or any `Fn(&str) -> usize`) when exact
+/// counts matter.
+pub trait TokenCounter {
+    fn count(&self, text: &str) -> usize;
+}
+
+/// Heuristic counter assuming roughly four characters per token
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CharEstimate;
+
+impl TokenCounter for CharEstimate {
+    fn count(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+impl<F> TokenCounter for F
+where
+    F: Fn(&str) -> usize,
+{
+    fn count(&self, text: &str) -> usize {
+        self(text)
+    }
+}
+
+/// Role of a conversation message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    System,
+    User,
+    Assistant,
+    Tool,
+}
+
+/// A single conversation message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub role: Role,
+    pub content: String,
+}
+
+impl Message {
+    pub fn new(role: Role, content: impl Into<String>) -> Self {
+        Self {
+            role,
+            content: content.into(),
+        }
+    }
+
+    pub fn system(content: impl Into<String>) -> Self {
+        Self::new(Role::System, content)
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        Self::new(Role::User, content)
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        Self::new(Role::Assistant, content)
+    }
+
+    pub fn tool(content: impl Into<String>) -> Self {
+        Self::new(Role::Tool, content)
+    }
+}
+
+/// Total token count of a list of messages
+pub fn count_messages<C>(messages: &[Message], counter: &C) -> usize
+where
+    C: TokenCounter + ?Sized,
+{
+    messages
+        .iter()
+        .map(|message| counter.count(&message.content))
+        .sum()
+}
+
+/// Drop the oldest non-system messages until `messages` fit in `max_tokens`.
+///
+/// System messages are never dropped, so the result can still exceed the
+/// budget if the system messages alone do. Returns the number of messages
+/// removed.
+pub fn truncate_messages<C>(messages: &mut Vec<Message>, max_tokens: usize, counter: &C) -> usize
+where
+    C: TokenCounter + ?Sized,
+{
+    let mut total = count_messages(messages, counter);
+    let mut removed = 0;
+
+    while total > max_tokens {
+        let Some(oldest) = messages
+            .iter()
+            .position(|message| message.role != Role::System)
+        else {
+            break;
+        };
+        total -= counter.count(&messages.remove(oldest).content);
+        removed += 1;
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn test_char_estimate() {
+        assert_eq!(CharEstimate.count(""), 0);
+        assert_eq!(CharEstimate.count("abcd"), 1);
+        assert_eq!(CharEstimate.count("abcde"), 2);
+    }
+
+    #[test]
+    fn test_truncate_drops_oldest_first() {
+        let mut messages = vec![
+            Message::system("be brief"),
+            Message::user("one two three"),
+            Message::assistant("four five"),
+            Message::user("six"),
+        ];
+
+        let removed = truncate_messages(&mut messages, 4, &words);
+        assert_eq!(removed, 2);
+        assert_eq!(
+            messages,
+            vec![Message::system("be brief"), Message::user("six")]
+        );
+    }
+
+    #[test]
+    fn test_truncate_never_drops_system() {
+        let mut messages = vec![
+            Message::system("a long system prompt that is over budget"),
+            Message::user("hello"),
+        ];
+
+        truncate_messages(&mut messages, 1, &words);
+        assert_eq!(
+            messages,
+            vec![Message::system("a long system prompt that is over budget")]
+        );
+    }
+
+    #[test]
+    fn test_truncate_within_budget_is_noop() {
+        let mut messages = vec![Message::system("hi"), Message::user("hello")];
+
+        assert_eq!(truncate_messages(&mut messages, 100, &CharEstimate), 0);
+        assert_eq!(messages.len(), 2);
+    }
+}
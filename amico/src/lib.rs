@@ -51,6 +51,8 @@
 
 use std::future::Future;
 
+pub mod time;
+
 // Re-export all layers
 pub use amico_models as models;
 pub use amico_plugin as plugin;
@@ -66,6 +68,8 @@ pub use amico_system::{Observable, Permission, SystemEffect, Tool};
 pub use amico_workflows::{AgentResponse, ToolLoopAgent, WorkflowError};
 
 /// Timestamp in milliseconds since epoch
+///
+/// Use [`time::now_millis`] to read the current time on any platform.
 pub type Timestamp = u64;
 
 /// Event metadata
@@ -158,7 +162,7 @@ pub trait EventRouter {
     ) -> impl Future<Output = Result<(), DispatchError>> + Send + 'a;
 }
 
-/// Common event types
+// Common event types
 
 /// Message event (e.g., from chat, social media, etc.)
 #[derive(Debug, Clone)]
@@ -167,6 +171,85 @@ pub struct MessageEvent {
     pub sender: String,
     pub timestamp: Timestamp,
     pub metadata: EventMetadata,
+    /// Id shared by a request and its response, for matching them up in
+    /// async dispatch
+    pub correlation_id: Option<String>,
+}
+
+impl MessageEvent {
+    /// Start building a message event
+    pub fn builder() -> MessageEventBuilder {
+        MessageEventBuilder::default()
+    }
+}
+
+/// Builder for [`MessageEvent`].
+///
+/// The timestamp defaults to the time of `build()` and the metadata
+/// source defaults to the sender.
+///
+/// ```rust,ignore
+/// let request = MessageEvent::builder()
+///     .content("What's the weather?")
+///     .sender("alice")
+///     .correlation_id("req-1")
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MessageEventBuilder {
+    content: String,
+    sender: String,
+    timestamp: Option<Timestamp>,
+    metadata: Option<EventMetadata>,
+    correlation_id: Option<String>,
+}
+
+impl MessageEventBuilder {
+    pub fn content(mut self, content: impl Into<String>) -> Self {
+        self.content = content.into();
+        self
+    }
+
+    pub fn sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = sender.into();
+        self
+    }
+
+    /// Set an explicit timestamp instead of the current time
+    pub fn timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn metadata(mut self, metadata: EventMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    pub fn correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Mark the message as a response to `request`, reusing its
+    /// correlation id
+    pub fn in_reply_to(mut self, request: &MessageEvent) -> Self {
+        self.correlation_id = request.correlation_id.clone();
+        self
+    }
+
+    pub fn build(self) -> MessageEvent {
+        let metadata = self
+            .metadata
+            .unwrap_or_else(|| EventMetadata::new(self.sender.clone()));
+        MessageEvent {
+            content: self.content,
+            sender: self.sender,
+            timestamp: self.timestamp.unwrap_or_else(time::now_millis),
+            metadata,
+            correlation_id: self.correlation_id,
+        }
+    }
 }
 
 impl Event for MessageEvent {
@@ -215,6 +298,34 @@ pub struct BlockchainEvent {
     pub metadata: EventMetadata,
 }
 
+/// Blockchain event decoding error (requires the `json` feature)
+#[cfg(feature = "json")]
+#[derive(Debug)]
+pub enum DecodeError {
+    /// `event_data` is not valid JSON for the requested type
+    Json(serde_json::Error),
+}
+
+#[cfg(feature = "json")]
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Json(err) => write!(f, "Failed to decode event data: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::error::Error for DecodeError {}
+
+#[cfg(feature = "json")]
+impl BlockchainEvent {
+    /// Decode JSON-encoded `event_data` into a typed log
+    pub fn decode<T: serde::de::DeserializeOwned>(&self) -> Result<T, DecodeError> {
+        serde_json::from_slice(&self.event_data).map_err(DecodeError::Json)
+    }
+}
+
 impl Event for BlockchainEvent {
     fn event_type(&self) -> &str {
         "blockchain"
@@ -291,3 +402,76 @@ pub trait EventInterceptor: Plugin {
         event: &'a Self::Event,
     ) -> impl Future<Output = Result<(), Self::Error>> + Send + 'a;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_event_builder() {
+        let before = time::now_millis();
+        let event = MessageEvent::builder()
+            .content("hello")
+            .sender("alice")
+            .build();
+
+        assert_eq!(event.content, "hello");
+        assert_eq!(event.sender, "alice");
+        assert_eq!(event.metadata.source, "alice");
+        assert_eq!(event.correlation_id, None);
+        assert!(event.timestamp >= before);
+    }
+
+    #[test]
+    fn test_message_event_correlation() {
+        let request = MessageEvent::builder()
+            .content("ping")
+            .sender("alice")
+            .timestamp(1_000)
+            .metadata(EventMetadata::new("chat").with_tags(vec!["dm".to_string()]))
+            .correlation_id("req-1")
+            .build();
+        assert_eq!(request.timestamp, 1_000);
+        assert_eq!(request.metadata.source, "chat");
+
+        let response = MessageEvent::builder()
+            .content("pong")
+            .sender("agent")
+            .in_reply_to(&request)
+            .build();
+        assert_eq!(response.correlation_id.as_deref(), Some("req-1"));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_blockchain_event_decode() {
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct Transfer {
+            from: String,
+            to: String,
+            amount: u64,
+        }
+
+        let mut event = BlockchainEvent {
+            chain: "solana".to_string(),
+            transaction_hash: "5xyz".to_string(),
+            event_data: br#"{"from":"alice","to":"bob","amount":42}"#.to_vec(),
+            timestamp: 0,
+            metadata: EventMetadata::new("solana"),
+        };
+        assert_eq!(
+            event.decode::<Transfer>().unwrap(),
+            Transfer {
+                from: "alice".to_string(),
+                to: "bob".to_string(),
+                amount: 42,
+            }
+        );
+
+        event.event_data = b"\x01\x02".to_vec();
+        assert!(matches!(
+            event.decode::<Transfer>(),
+            Err(DecodeError::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,35 @@
+//! Clock helpers for event producers
+//!
+//! `std::time::SystemTime` and `Instant` panic on `wasm32-unknown-unknown`,
+//! so browser builds read the clock through `js_sys::Date` instead.
+
+use crate::Timestamp;
+
+/// Monotonic clock for measuring durations, shared with the lower layers
+pub use amico_runtime::Monotonic;
+
+/// Wall-clock time in milliseconds since the Unix epoch
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub fn now_millis() -> Timestamp {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as Timestamp)
+        .unwrap_or(0)
+}
+
+/// Wall-clock time in milliseconds since the Unix epoch
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub fn now_millis() -> Timestamp {
+    js_sys::Date::now() as Timestamp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_now_millis_is_after_2020() {
+        // 2020-01-01T00:00:00Z
+        assert!(now_millis() > 1_577_836_800_000);
+    }
+}